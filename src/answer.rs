@@ -0,0 +1,95 @@
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i64),
+    Big(i128),
+    Text(String),
+}
+
+impl Answer {
+    // Reads an answer back from its displayed form; anything that wouldn't display the same way as
+    // a number (e.g. with leading zeros) is kept as text.
+    pub fn parse(string: &str) -> Self {
+        match string.parse::<i128>() {
+            Ok(n) if n.to_string() == string => Self::from(n),
+            _ => Self::Text(string.to_string()),
+        }
+    }
+
+    pub fn as_integer(&self) -> Option<i128> {
+        match self {
+            Self::Int(n) => Some(*n as i128),
+            Self::Big(n) => Some(*n),
+            Self::Text(_) => None,
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Int(n) => write!(f, "{}", n),
+            Self::Big(n) => write!(f, "{}", n),
+            Self::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($int:ty),+) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(n: $int) -> Self {
+                    match i64::try_from(n) {
+                        Ok(n) => Self::Int(n),
+                        Err(_) => Self::Big(n as i128),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_from_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_string())
+    }
+}
+
+impl FromIterator<char> for Answer {
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> Self {
+        Self::Text(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod test_answer {
+    use super::*;
+
+    #[test]
+    fn test_answer_values() {
+        assert_eq!(Answer::from(24000u32), Answer::Int(24000));
+        assert_eq!(Answer::from(1i128 << 80), Answer::Big(1 << 80));
+        assert_eq!(Answer::parse("1514285714288"), Answer::Int(1514285714288));
+        assert_eq!(
+            Answer::parse("1208925819614629174706176"),
+            Answer::Big(1 << 80)
+        );
+        assert_eq!(Answer::parse("007"), Answer::from("007"));
+        assert_eq!(Answer::parse("2=-1=0"), Answer::from("2=-1=0"));
+        assert_eq!(
+            Answer::Big(1 << 80).to_string(),
+            "1208925819614629174706176"
+        );
+        assert_eq!(Answer::parse("-12").as_integer(), Some(-12));
+    }
+}
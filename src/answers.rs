@@ -0,0 +1,98 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{answer::Answer, config::Config, day::Part, report::DayReport};
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    part1: Option<Answer>,
+    part2: Option<Answer>,
+}
+
+impl Answers {
+    fn path(config: &Config, day: usize) -> PathBuf {
+        config.cache_file(format!("day{}.ans", day))
+    }
+
+    pub fn parse(string: &str) -> Self {
+        let mut answers = Self::default();
+        for (key, answer) in string.lines().filter_map(|line| line.split_once(": ")) {
+            match key {
+                "part1" => answers.set(Part::One, Answer::parse(answer)),
+                "part2" => answers.set(Part::Two, Answer::parse(answer)),
+                _ => {}
+            }
+        }
+        answers
+    }
+
+    pub fn load(config: &Config, day: usize) -> Self {
+        Self::load_from(&Self::path(config, day))
+    }
+
+    pub fn load_from(path: &Path) -> Self {
+        Self::parse(&fs::read_to_string(path).unwrap_or_default())
+    }
+
+    pub fn save(&self, config: &Config, day: usize) -> io::Result<()> {
+        self.save_to(&Self::path(config, day))
+    }
+
+    pub fn save_to(&self, path: &Path) -> io::Result<()> {
+        let contents = [Part::One, Part::Two]
+            .into_iter()
+            .filter_map(|part| Some(format!("part{}: {}\n", part.number(), self.get(part)?)))
+            .collect::<String>();
+        fs::write(path, contents)
+    }
+
+    pub fn get(&self, part: Part) -> Option<&Answer> {
+        match part {
+            Part::One => self.part1.as_ref(),
+            Part::Two => self.part2.as_ref(),
+        }
+    }
+
+    pub fn set(&mut self, part: Part, answer: Answer) {
+        match part {
+            Part::One => self.part1 = Some(answer),
+            Part::Two => self.part2 = Some(answer),
+        }
+    }
+}
+
+pub fn check(config: &Config, report: &mut DayReport) {
+    let answers = Answers::load(config, report.day);
+    for part in report.parts.iter_mut() {
+        part.expected = answers.get(part.part).cloned();
+    }
+}
+
+pub fn record(config: &Config, report: &DayReport) -> io::Result<()> {
+    let mut answers = Answers::load(config, report.day);
+    for part in report.parts.iter() {
+        answers.set(part.part, part.answer.clone());
+    }
+    answers.save(config, report.day)
+}
+
+#[cfg(test)]
+mod test_answers {
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = Answers::parse("part1: CMZ\npart2: MCD\n");
+        assert_eq!(answers.get(Part::One), Some(&Answer::from("CMZ")));
+        assert_eq!(answers.get(Part::Two), Some(&Answer::from("MCD")));
+
+        let answers = Answers::parse("part1: 1514285714288\npart2: Merry Christmas!\n");
+        assert_eq!(answers.get(Part::One), Some(&Answer::Int(1514285714288)));
+        assert_eq!(
+            answers.get(Part::Two),
+            Some(&Answer::from("Merry Christmas!"))
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use std::{collections::BTreeMap, fs, io, path::PathBuf, time::Duration};
+
+use crate::{bench::DayBench, config::Config};
+
+// The median time of each benchmarked phase, keyed by day and phase.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Baseline {
+    medians: BTreeMap<(usize, String), Duration>,
+}
+
+impl Baseline {
+    pub fn path(config: &Config) -> PathBuf {
+        config.cache_file("bench.baseline".to_string())
+    }
+
+    pub fn parse(string: &str) -> Self {
+        let mut baseline = Self::default();
+        for (key, nanos) in string.lines().filter_map(|line| line.split_once(": ")) {
+            let Some((day, phase)) = key.split_once(' ') else {
+                continue;
+            };
+            let (Some(Ok(day)), Ok(nanos)) =
+                (day.strip_prefix("day").map(str::parse), nanos.parse())
+            else {
+                continue;
+            };
+            baseline
+                .medians
+                .insert((day, phase.to_string()), Duration::from_nanos(nanos));
+        }
+        baseline
+    }
+
+    pub fn load(config: &Config) -> io::Result<Self> {
+        Ok(Self::parse(&fs::read_to_string(Self::path(config))?))
+    }
+
+    pub fn save(&self, config: &Config) -> io::Result<()> {
+        let contents = self
+            .medians
+            .iter()
+            .map(|((day, phase), median)| format!("day{} {}: {}\n", day, phase, median.as_nanos()))
+            .collect::<String>();
+        fs::write(Self::path(config), contents)
+    }
+
+    pub fn update(&mut self, bench: &DayBench) {
+        for (phase, measurement) in bench.phases() {
+            self.medians
+                .insert((bench.day, phase.to_string()), measurement.stats.median);
+        }
+    }
+
+    // The change in a phase's median time relative to the baseline, as a fraction (so 0.1 is 10%
+    // slower).
+    pub fn change(&self, day: usize, phase: &str, median: Duration) -> Option<f64> {
+        let baseline = self.medians.get(&(day, phase.to_string()))?;
+        Some(median.as_nanos() as f64 / baseline.as_nanos() as f64 - 1.0)
+    }
+
+    // The phases of a day that are slower than the baseline by more than `threshold` percent.
+    pub fn regressions(&self, bench: &DayBench, threshold: u32) -> Vec<&'static str> {
+        bench
+            .phases()
+            .into_iter()
+            .filter(|(phase, measurement)| {
+                self.change(bench.day, phase, measurement.stats.median)
+                    .is_some_and(|change| change > threshold as f64 / 100.0)
+            })
+            .map(|(phase, _)| phase)
+            .collect()
+    }
+}
+
+// A run of benchmarks, compared against the saved baseline and collected into an updated one to
+// save afterwards, where days that weren't benchmarked this time keep their saved timings.
+pub struct BaselineRun {
+    pub compared: Option<Baseline>,
+    updated: Option<Baseline>,
+    pub threshold: u32,
+    pub regressed: Vec<String>,
+}
+
+impl BaselineRun {
+    pub fn new(config: &Config, compare: bool, save: bool, threshold: u32) -> Result<Self, String> {
+        let compared = match compare {
+            true => Some(Baseline::load(config).map_err(|err| {
+                format!(
+                    "Couldn't read benchmark baseline {} [{}] (save one with --save-baseline)",
+                    Baseline::path(config).display(),
+                    err
+                )
+            })?),
+            false => None,
+        };
+        let updated = match save {
+            true => Some(Baseline::load(config).unwrap_or_default()),
+            false => None,
+        };
+        Ok(Self {
+            compared,
+            updated,
+            threshold,
+            regressed: Vec::new(),
+        })
+    }
+
+    pub fn add(&mut self, bench: &DayBench) {
+        if let Some(compared) = &self.compared {
+            for phase in compared.regressions(bench, self.threshold) {
+                self.regressed
+                    .push(format!("day{:02} {}", bench.day, phase));
+            }
+        }
+        if let Some(updated) = &mut self.updated {
+            updated.update(bench);
+        }
+    }
+
+    // Saves the updated baseline if one was asked for, returning where it went.
+    pub fn save(&self, config: &Config) -> Result<Option<PathBuf>, String> {
+        let Some(updated) = &self.updated else {
+            return Ok(None);
+        };
+        let path = Baseline::path(config);
+        updated.save(config).map_err(|err| {
+            format!(
+                "Couldn't save benchmark baseline {} [{}]",
+                path.display(),
+                err
+            )
+        })?;
+        Ok(Some(path))
+    }
+}
+
+#[cfg(test)]
+mod test_baseline {
+    use super::*;
+    use crate::{bench::Measurement, stats::Stats};
+    use std::{env, process};
+
+    fn measurement(micros: u64) -> Measurement {
+        Measurement {
+            stats: Stats::new(&[Duration::from_micros(micros)]).unwrap(),
+            outliers: 0,
+        }
+    }
+
+    #[test]
+    fn test_baseline() {
+        let baseline = Baseline::parse("day16 parse: 1000\nday16 part1: 2000000\nnonsense\n");
+        assert_eq!(baseline.medians.len(), 2);
+        assert_eq!(
+            baseline.change(16, "part1", Duration::from_millis(3)),
+            Some(0.5)
+        );
+        assert_eq!(baseline.change(16, "part2", Duration::from_millis(3)), None);
+
+        let bench = DayBench {
+            day: 16,
+            parse: measurement(1),
+            part1: measurement(2050),
+            part2: measurement(5),
+        };
+        assert_eq!(baseline.regressions(&bench, 10), Vec::<&str>::new());
+        assert_eq!(baseline.regressions(&bench, 2), ["part1"]);
+
+        let mut updated = Baseline::default();
+        updated.update(&bench);
+        assert_eq!(
+            updated.change(16, "part2", Duration::from_micros(5)),
+            Some(0.0)
+        );
+    }
+
+    #[test]
+    fn test_baseline_run() {
+        let cache_dir = env::temp_dir().join(format!("aoc2022-baseline-{}", process::id()));
+        fs::create_dir_all(&cache_dir).unwrap();
+        let config = Config {
+            cache_dir: cache_dir.clone(),
+            ..Config::default()
+        };
+        assert!(BaselineRun::new(&config, true, false, 10).is_err());
+
+        let bench = |part1| DayBench {
+            day: 16,
+            parse: measurement(1),
+            part1: measurement(part1),
+            part2: measurement(5),
+        };
+        let mut run = BaselineRun::new(&config, false, true, 10).unwrap();
+        run.add(&bench(2000));
+        assert_eq!(run.save(&config).unwrap(), Some(Baseline::path(&config)));
+
+        let mut run = BaselineRun::new(&config, true, false, 10).unwrap();
+        run.add(&bench(2100));
+        run.add(&bench(2500));
+        assert_eq!(run.regressed, ["day16 part1"]);
+        assert_eq!(run.save(&config).unwrap(), None);
+        fs::remove_dir_all(cache_dir).unwrap();
+    }
+}
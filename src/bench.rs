@@ -0,0 +1,150 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::{baseline::Baseline, stats::Stats};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Measurement {
+    pub stats: Stats,
+    pub outliers: usize,
+}
+
+impl Display for Measurement {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}, {} outlier(s) rejected", self.stats, self.outliers)
+    }
+}
+
+pub struct Harness {
+    pub warmup: Duration,
+    pub samples: usize,
+}
+
+impl Default for Harness {
+    fn default() -> Self {
+        Self {
+            warmup: Duration::from_millis(500),
+            samples: 50,
+        }
+    }
+}
+
+impl Harness {
+    // Runs the routine until the warmup time has passed and then times it on `samples` fresh
+    // inputs; only the routine itself is timed, not setting up its input or dropping its output.
+    pub fn measure<I, T>(
+        &self,
+        mut setup: impl FnMut() -> I,
+        mut routine: impl FnMut(I) -> T,
+    ) -> Measurement {
+        let start = Instant::now();
+        while start.elapsed() < self.warmup {
+            black_box(routine(black_box(setup())));
+        }
+        let mut samples = (0..self.samples.max(1))
+            .map(|_| {
+                let input = black_box(setup());
+                let start = Instant::now();
+                let output = routine(input);
+                let elapsed = start.elapsed();
+                black_box(output);
+                elapsed
+            })
+            .collect::<Vec<_>>();
+        let outliers = reject_outliers(&mut samples);
+        Measurement {
+            stats: Stats::new(&samples).unwrap(),
+            outliers,
+        }
+    }
+}
+
+// Discards samples outside Tukey's fences, more than 1.5 interquartile ranges beyond the quartiles.
+pub fn reject_outliers(samples: &mut Vec<Duration>) -> usize {
+    samples.sort();
+    let n = samples.len();
+    if n < 4 {
+        return 0;
+    }
+    let (q1, q3) = (samples[n / 4], samples[3 * n / 4]);
+    let fence = (q3 - q1) * 3 / 2;
+    let (low, high) = (q1.saturating_sub(fence), q3 + fence);
+    samples.retain(|sample| (low..=high).contains(sample));
+    n - samples.len()
+}
+
+pub struct DayBench {
+    pub day: usize,
+    pub parse: Measurement,
+    pub part1: Measurement,
+    pub part2: Measurement,
+}
+
+impl DayBench {
+    pub fn phases(&self) -> [(&'static str, Measurement); 3] {
+        [
+            ("parse", self.parse),
+            ("part1", self.part1),
+            ("part2", self.part2),
+        ]
+    }
+
+    pub fn print(&self, baseline: Option<&Baseline>, threshold: u32) {
+        println!();
+        println!("day{:02}:", self.day);
+        let regressions = baseline.map_or(Vec::new(), |b| b.regressions(self, threshold));
+        for (phase, measurement) in self.phases() {
+            let change = baseline.map(|b| b.change(self.day, phase, measurement.stats.median));
+            let comparison = match change {
+                None => String::new(),
+                Some(None) => " [not in baseline]".to_string(),
+                Some(Some(change)) => format!(
+                    " [{:+.1}% on baseline{}]",
+                    100.0 * change,
+                    if regressions.contains(&phase) {
+                        ": REGRESSED"
+                    } else {
+                        ""
+                    }
+                ),
+            };
+            println!("  {}: {}{}", phase, measurement, comparison);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_bench {
+    use super::*;
+
+    #[test]
+    fn test_reject_outliers() {
+        let mut samples = [10, 11, 12, 10, 11, 13, 12, 95, 11, 1]
+            .map(Duration::from_micros)
+            .to_vec();
+        assert_eq!(reject_outliers(&mut samples), 2);
+        assert_eq!(samples.first(), Some(&Duration::from_micros(10)));
+        assert_eq!(samples.last(), Some(&Duration::from_micros(13)));
+    }
+
+    #[test]
+    fn test_measure() {
+        let harness = Harness {
+            warmup: Duration::ZERO,
+            samples: 8,
+        };
+        let mut setups = 0;
+        let measurement = harness.measure(
+            || {
+                setups += 1;
+                vec![3, 1, 2]
+            },
+            |mut v| v.sort(),
+        );
+        assert_eq!(setups, 8);
+        assert_eq!(measurement.stats.runs + measurement.outliers, 8);
+    }
+}
@@ -0,0 +1,233 @@
+use std::{
+    env, error,
+    fmt::{self, Display, Formatter},
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+#[derive(Debug)]
+pub enum Error {
+    Reading(PathBuf, io::Error),
+    Invalid(String, String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Reading(path, err) => {
+                write!(f, "Couldn't read config file {} [{}]", path.display(), err)
+            }
+            Self::Invalid(source, err) => write!(f, "Invalid configuration in {}: {}", source, err),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub base_url: String,
+    pub year: u32,
+    pub session: Option<String>,
+    pub request_delay: Duration,
+    pub cache_dir: PathBuf,
+    pub regression_threshold: u32,
+    pub timeout: Option<Duration>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            base_url: "https://adventofcode.com".to_string(),
+            year: 2022,
+            session: None,
+            request_delay: Duration::from_secs(3),
+            cache_dir: PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/cache")),
+            regression_threshold: 10,
+            timeout: None,
+        }
+    }
+}
+
+impl Config {
+    // Settings come from the config file, then environment variables, each overriding the last.
+    pub fn load() -> Result<Self, Error> {
+        let mut config = Self::default();
+        if let Some(path) = Self::path() {
+            match fs::read_to_string(&path) {
+                Ok(contents) => config.read_file(&contents, &path)?,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(Error::Reading(path, err)),
+            }
+        }
+        config.read_env(|name| env::var(name).ok())?;
+        Ok(config)
+    }
+
+    pub fn path() -> Option<PathBuf> {
+        if let Some(path) = env::var_os("AOC_CONFIG") {
+            return Some(PathBuf::from(path));
+        }
+        let config_home = match env::var_os("XDG_CONFIG_HOME") {
+            Some(dir) => PathBuf::from(dir),
+            None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+        };
+        Some(config_home.join("aoc2022").join("config"))
+    }
+
+    pub fn page_url(&self, day: usize) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day)
+    }
+
+    pub fn input_url(&self, day: usize) -> String {
+        format!("{}/input", self.page_url(day))
+    }
+
+    pub fn answer_url(&self, day: usize) -> String {
+        format!("{}/answer", self.page_url(day))
+    }
+
+    pub fn cache_file(&self, name: String) -> PathBuf {
+        self.cache_dir.join(name)
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "base_url" => self.base_url = value.trim_end_matches('/').to_string(),
+            "year" => {
+                self.year = value
+                    .parse()
+                    .map_err(|_| format!("expected a year, found {:?}", value))?
+            }
+            // an empty session is the same as leaving it out
+            "session" => self.session = Some(value.to_string()).filter(|s| !s.is_empty()),
+            "cache_dir" => self.cache_dir = PathBuf::from(value),
+            "request_delay" => {
+                self.request_delay = value
+                    .parse()
+                    .ok()
+                    .filter(|&secs: &f64| secs >= 0.0 && secs.is_finite())
+                    .map(Duration::from_secs_f64)
+                    .ok_or_else(|| format!("expected a number of seconds, found {:?}", value))?
+            }
+            "regression_threshold" => {
+                self.regression_threshold = value
+                    .trim_end_matches('%')
+                    .parse()
+                    .map_err(|_| format!("expected a percentage, found {:?}", value))?
+            }
+            "timeout" => {
+                self.timeout = parse_timeout(value)
+                    .ok_or_else(|| format!("expected a number of seconds, found {:?}", value))?
+            }
+            _ => return Err(format!("unknown setting {:?}", key)),
+        }
+        Ok(())
+    }
+
+    fn read_file(&mut self, contents: &str, path: &Path) -> Result<(), Error> {
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let source = || format!("{} (line {})", path.display(), i + 1);
+            let (key, value) = line.split_once('=').ok_or_else(|| {
+                Error::Invalid(source(), format!("expected key = value, found {:?}", line))
+            })?;
+            self.set(key.trim(), value.trim())
+                .map_err(|err| Error::Invalid(source(), err))?;
+        }
+        Ok(())
+    }
+
+    fn read_env<F: Fn(&str) -> Option<String>>(&mut self, var: F) -> Result<(), Error> {
+        for (name, key) in [
+            ("AOC_BASE_URL", "base_url"),
+            ("AOC_YEAR", "year"),
+            ("AOC_REQUEST_DELAY", "request_delay"),
+            ("AOC_CACHE_DIR", "cache_dir"),
+            ("AOC_REGRESSION_THRESHOLD", "regression_threshold"),
+            ("AOC_TIMEOUT", "timeout"),
+        ] {
+            if let Some(value) = var(name) {
+                self.set(key, &value)
+                    .map_err(|err| Error::Invalid(name.to_string(), err))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// A timeout of 0 seconds means there isn't one.
+pub fn parse_timeout(secs: &str) -> Option<Option<Duration>> {
+    let secs = secs.parse::<f64>().ok()?;
+    if secs == 0.0 {
+        Some(None)
+    } else if secs > 0.0 && secs.is_finite() {
+        Some(Some(Duration::from_secs_f64(secs)))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test_config {
+    use super::*;
+
+    #[test]
+    fn test_config_sources() {
+        let mut config = Config::default();
+        assert_eq!(
+            config.input_url(3),
+            "https://adventofcode.com/2022/day/3/input"
+        );
+
+        let path = Path::new("config");
+        config
+            .read_file(
+                "# mock server\nbase_url = http://localhost:8080/\nyear=2021\n",
+                path,
+            )
+            .unwrap();
+        assert_eq!(
+            config.input_url(3),
+            "http://localhost:8080/2021/day/3/input"
+        );
+        assert_eq!(config.session, None);
+        config
+            .read_file("session = 53616c7465645f5f\n", path)
+            .unwrap();
+        assert_eq!(config.session.as_deref(), Some("53616c7465645f5f"));
+        config.read_file("session =\n", path).unwrap();
+        assert_eq!(config.session, None);
+        config.read_file("request_delay = 0.5\n", path).unwrap();
+        assert_eq!(config.request_delay, Duration::from_millis(500));
+        assert!(config.read_file("request_delay = -1\n", path).is_err());
+        config.read_file("cache_dir = /tmp/aoc\n", path).unwrap();
+        assert_eq!(
+            config.cache_file("day3.in".to_string()),
+            Path::new("/tmp/aoc/day3.in")
+        );
+        config
+            .read_file("regression_threshold = 5%\n", path)
+            .unwrap();
+        assert_eq!(config.regression_threshold, 5);
+        config.read_file("timeout = 30\n", path).unwrap();
+        assert_eq!(config.timeout, Some(Duration::from_secs(30)));
+        config.read_file("timeout = 0\n", path).unwrap();
+        assert_eq!(config.timeout, None);
+        assert!(config.read_file("timeout = forever\n", path).is_err());
+        assert!(config.read_file("year = soon\n", path).is_err());
+        assert!(config.read_file("colour = red\n", path).is_err());
+
+        config
+            .read_env(|name| (name == "AOC_YEAR").then(|| "2015".to_string()))
+            .unwrap();
+        assert_eq!(
+            config.input_url(3),
+            "http://localhost:8080/2015/day/3/input"
+        );
+    }
+}
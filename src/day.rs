@@ -1,93 +1,108 @@
-use reqwest::{self, header::COOKIE};
+use reqwest::{self, StatusCode};
 use std::{
     error,
     fmt::{self, Display, Formatter},
-    fs, io,
-    time::Instant,
+    fs,
+    path::PathBuf,
 };
 
+use crate::{answer::Answer, config::Config, parse::ParseError, web};
+
 #[derive(Debug)]
 pub enum Error {
-    MissingCookie(io::Error),
+    MissingCookie(Vec<String>),
     DownloadingInput(reqwest::Error),
+    DownloadingPage(reqwest::Error),
+    SubmittingAnswer(reqwest::Error),
+    UnrecognisedResponse,
+    NotUnlocked,
+    SessionRejected,
+    BadStatus(StatusCode, String),
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self {
-            Self::MissingCookie(err) => write!(
+            Self::MissingCookie(checked) => {
+                write!(
+                    f,
+                    "Missing session cookie to access input; please set AOC_SESSION, \
+                    create a text file .session_cookie in the cache directory or add a line \
+                    `session = ...` to the config file, containing your session \
+                    cookie for adventofcode.com. Checked:"
+                )?;
+                checked
+                    .iter()
+                    .try_for_each(|place| write!(f, "\n  {}", place))
+            }
+            Self::DownloadingInput(err) => write!(f, "Couldn't download input... [{}]", err),
+            Self::DownloadingPage(err) => write!(f, "Couldn't download puzzle page [{}]", err),
+            Self::SubmittingAnswer(err) => write!(f, "Couldn't submit answer [{}]", err),
+            Self::UnrecognisedResponse => {
+                write!(
+                    f,
+                    "Couldn't make sense of the response to the submitted answer"
+                )
+            }
+            Self::NotUnlocked => write!(f, "Input isn't available until the puzzle unlocks"),
+            Self::SessionRejected => write!(
                 f,
-                "Missing session cookie to access input; please \
-                create a text file at cache/.session_cookie containing \
-                your session cookie for adventofcode.com [{}]",
-                err
+                "The server didn't accept the session cookie; it may have expired, \
+                so please log in to adventofcode.com again and update it"
             ),
-            Self::DownloadingInput(err) => write!(f, "Couldn't download input... [{}]", err),
+            Self::BadStatus(status, body) => {
+                write!(f, "Request failed, server responded {}", status)?;
+                match body.lines().next() {
+                    Some(line) if !line.trim().is_empty() => write!(f, " [{}]", line.trim()),
+                    _ => Ok(()),
+                }
+            }
         }
     }
 }
 
 impl error::Error for Error {}
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn number(self) -> usize {
+        match self {
+            Self::One => 1,
+            Self::Two => 2,
+        }
+    }
+}
+
+pub fn input_path(config: &Config, day: usize) -> PathBuf {
+    config.cache_file(format!("day{}.in", day))
+}
+
 pub trait Day<'a> {
     const DAY: usize;
-    type Input;
-    type ProcessedInput;
+    type Input: Clone;
+    type ProcessedInput: Clone;
 
-    fn parse(input: &'a str) -> Self::Input;
-    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, String);
-    fn solve_part2(input: Self::ProcessedInput) -> String;
+    fn parse(input: &'a str) -> Result<Self::Input, ParseError>;
+    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, Answer);
+    fn solve_part2(input: Self::ProcessedInput) -> Answer;
 
-    fn get_input() -> Result<String, Error> {
-        let input_path = format!("cache/day{}.in", Self::DAY);
+    // Prepares the input for part 2 when part 1 isn't being run; days whose part 2 doesn't need
+    // anything computed by part 1 should override this to avoid solving part 1.
+    fn process(input: Self::Input) -> Self::ProcessedInput {
+        Self::solve_part1(input).0
+    }
+
+    fn get_input(config: &Config) -> Result<String, Error> {
+        let input_path = input_path(config, Self::DAY);
         fs::read_to_string(&input_path).or_else(|_| {
-            let cookie = format!(
-                "session={}",
-                fs::read_to_string("cache/.session_cookie")
-                    .map_err(Error::MissingCookie)?
-                    .trim()
-            );
-            let input = reqwest::blocking::Client::new()
-                .get(format!(
-                    "https://adventofcode.com/2022/day/{}/input",
-                    Self::DAY
-                ))
-                .header(COOKIE, cookie)
-                .send()
-                .map_err(Error::DownloadingInput)?
-                .text()
-                .map_err(Error::DownloadingInput)?;
+            let input = web::download_input(config, Self::DAY)?;
             drop(fs::write(input_path, &input));
             Ok(input)
         })
     }
-
-    fn solve_and_print(input: &'a str) {
-        println!();
-        println!("day{:02}:", Self::DAY);
-
-        let start_time = Instant::now();
-        let input = Self::parse(input);
-        let parsed_time = Instant::now();
-        println!(
-            "  parsing: ... (elapsed {}ms)",
-            1000.0 * (parsed_time - start_time).as_secs_f32()
-        );
-
-        let (processed_input, part1_answer) = Self::solve_part1(input);
-        let part1_time = Instant::now();
-        println!(
-            "  part1: {} (elapsed {}ms)",
-            part1_answer,
-            1000.0 * (part1_time - parsed_time).as_secs_f32()
-        );
-
-        let part2_answer = Self::solve_part2(processed_input);
-        let part2_time = Instant::now();
-        println!(
-            "  part2: {} (elapsed {}ms)",
-            part2_answer,
-            1000.0 * (part2_time - part1_time).as_secs_f32()
-        )
-    }
 }
@@ -1,4 +1,8 @@
-use crate::day::Day;
+use crate::{
+    answer::Answer,
+    day::Day,
+    parse::{ParseError, Source},
+};
 
 pub struct Day01;
 
@@ -8,22 +12,28 @@ impl<'a> Day<'a> for Day01 {
     type Input = Vec<u32>;
     type ProcessedInput = Vec<u32>;
 
-    fn parse(input: &'a str) -> Self::Input {
+    fn parse(input: &'a str) -> Result<Self::Input, ParseError> {
+        let src = Source::new(Self::DAY, input);
         input
             .split("\n\n")
-            .map(|elf| elf.lines().map(|c| c.parse::<u32>().unwrap()).sum())
+            .map(|elf| elf.lines().map(|c| src.number::<u32>(c)).sum())
             .collect()
     }
 
-    fn solve_part1(mut totals: Self::Input) -> (Self::ProcessedInput, String) {
+    fn solve_part1(totals: Self::Input) -> (Self::ProcessedInput, Answer) {
+        let totals = Self::process(totals);
+        let ans = totals[0].into();
+        (totals, ans)
+    }
+
+    fn process(mut totals: Self::Input) -> Self::ProcessedInput {
         totals.sort();
         totals.reverse();
-        let ans = totals[0].to_string();
-        (totals, ans)
+        totals
     }
 
-    fn solve_part2(totals: Self::ProcessedInput) -> String {
-        totals.into_iter().take(3).sum::<u32>().to_string()
+    fn solve_part2(totals: Self::ProcessedInput) -> Answer {
+        totals.into_iter().take(3).sum::<u32>().into()
     }
 }
 
@@ -51,12 +61,17 @@ mod test_day01 {
 
     #[test]
     fn test_day01_examples() {
-        let input = Day01::parse(EXAMPLE);
+        let input = Day01::parse(EXAMPLE).unwrap();
         let (input, part1) = Day01::solve_part1(input);
         let part2 = Day01::solve_part2(input);
-        assert_eq!(part1, "24000");
-        assert_eq!(part2, "45000");
+        assert_eq!(part1, Answer::Int(24000));
+        assert_eq!(part2, Answer::Int(45000));
     }
-}
 
-bench_day!(01);
+    #[test]
+    fn test_day01_parse_errors() {
+        let err = Day01::parse("1000\n2000\n\n3000\nx\n").unwrap_err();
+        assert_eq!((err.line, err.column), (5, 1));
+        assert_eq!(err.expected, "a number");
+    }
+}
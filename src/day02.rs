@@ -1,4 +1,8 @@
-use crate::day::Day;
+use crate::{
+    answer::Answer,
+    day::Day,
+    parse::{ParseError, Source},
+};
 
 pub struct Day02;
 
@@ -7,33 +11,48 @@ impl<'a> Day<'a> for Day02 {
     type Input = Vec<(i32, i32)>;
     type ProcessedInput = Vec<(i32, i32)>;
 
-    fn parse(input: &'a str) -> Self::Input {
+    fn parse(input: &'a str) -> Result<Self::Input, ParseError> {
+        let src = Source::new(Self::DAY, input);
+        let shape = |s: &'a str, first: u8| match s.as_bytes() {
+            &[c] if (first..first + 3).contains(&c) => Ok((c - first) as i32),
+            _ => Err(src.error(
+                s,
+                format!(
+                    "{}, {} or {}",
+                    first as char,
+                    (first + 1) as char,
+                    (first + 2) as char
+                ),
+            )),
+        };
         input
             .lines()
             .map(|line| {
-                (
-                    (line.as_bytes()[0] - b'A') as i32,
-                    (line.as_bytes()[2] - b'X') as i32,
-                )
+                let (l, r) = src.split_once(line, " ")?;
+                Ok((shape(l, b'A')?, shape(r, b'X')?))
             })
             .collect()
     }
 
-    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, String) {
+    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, Answer) {
         let ans = input
             .iter()
             .map(|(l, r)| 3 * (r - l + 1).rem_euclid(3) + r + 1)
             .sum::<i32>()
-            .to_string();
+            .into();
         (input, ans)
     }
 
-    fn solve_part2(input: Self::ProcessedInput) -> String {
+    fn process(input: Self::Input) -> Self::ProcessedInput {
+        input
+    }
+
+    fn solve_part2(input: Self::ProcessedInput) -> Answer {
         input
             .iter()
             .map(|(l, r)| r * 3 + (l + r - 1).rem_euclid(3) + 1)
             .sum::<i32>()
-            .to_string()
+            .into()
     }
 }
 
@@ -50,12 +69,18 @@ mod test_day02 {
 
     #[test]
     fn test_day02_examples() {
-        let input = Day02::parse(EXAMPLE);
+        let input = Day02::parse(EXAMPLE).unwrap();
         let (input, part1) = Day02::solve_part1(input);
         let part2 = Day02::solve_part2(input);
-        assert_eq!(part1, "15");
-        assert_eq!(part2, "12");
+        assert_eq!(part1, Answer::Int(15));
+        assert_eq!(part2, Answer::Int(12));
     }
-}
 
-bench_day!(02);
+    #[test]
+    fn test_day02_parse_errors() {
+        let err = Day02::parse("A Y\nB Q\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.expected, "X, Y or Z");
+        assert_eq!(Day02::parse("A Y\nBX\n").unwrap_err().expected, "\" \"");
+    }
+}
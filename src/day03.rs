@@ -1,4 +1,8 @@
-use crate::day::Day;
+use crate::{
+    answer::Answer,
+    day::Day,
+    parse::{end_of, ParseError, Source},
+};
 
 fn priority(c: char) -> u32 {
     match c as u8 {
@@ -7,11 +11,10 @@ fn priority(c: char) -> u32 {
     }
 }
 
-fn common(strings: &[&str]) -> char {
+fn common(strings: &[&str]) -> Option<char> {
     strings[0]
         .chars()
         .find(|&c| strings[1..].iter().all(|s| s.contains(c)))
-        .unwrap()
 }
 
 pub struct Day03;
@@ -21,28 +24,51 @@ impl<'a> Day<'a> for Day03 {
     type Input = Vec<&'a str>;
     type ProcessedInput = Vec<&'a str>;
 
-    fn parse(input: &'a str) -> Self::Input {
-        input.lines().map(str::trim).collect()
+    fn parse(input: &'a str) -> Result<Self::Input, ParseError> {
+        let src = Source::new(Self::DAY, input);
+        let rucksacks = input
+            .lines()
+            .map(str::trim)
+            .map(|line| match line.find(|c: char| !c.is_ascii_alphabetic()) {
+                Some(i) => Err(src.error(&line[i..], "an item letter")),
+                None if line.len() % 2 != 0 => Err(src.error(line, "an even number of items")),
+                None if common(&[&line[..line.len() / 2], &line[line.len() / 2..]]).is_none() => {
+                    Err(src.error(line, "compartments sharing an item"))
+                }
+                None => Ok(line),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if rucksacks.len() % 3 != 0 {
+            return Err(src.error(end_of(input), "rucksacks in groups of three"));
+        }
+        match rucksacks.chunks(3).find(|group| common(group).is_none()) {
+            Some(group) => Err(src.error(group[0], "a group of rucksacks sharing a badge")),
+            None => Ok(rucksacks),
+        }
     }
 
-    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, String) {
+    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, Answer) {
         let ans = input
             .iter()
             .map(|line| [&line[..line.len() / 2], &line[line.len() / 2..]])
-            .map(|p| common(&p))
+            .map(|p| common(&p).unwrap())
             .map(priority)
             .sum::<u32>()
-            .to_string();
+            .into();
         (input, ans)
     }
 
-    fn solve_part2(input: Self::ProcessedInput) -> String {
+    fn process(input: Self::Input) -> Self::ProcessedInput {
+        input
+    }
+
+    fn solve_part2(input: Self::ProcessedInput) -> Answer {
         input
             .chunks(3)
-            .map(common)
+            .map(|group| common(group).unwrap())
             .map(priority)
             .sum::<u32>()
-            .to_string()
+            .into()
     }
 }
 
@@ -62,12 +88,26 @@ mod test_day03 {
 
     #[test]
     fn test_day03_examples() {
-        let input = Day03::parse(EXAMPLE);
+        let input = Day03::parse(EXAMPLE).unwrap();
         let (input, part1) = Day03::solve_part1(input);
         let part2 = Day03::solve_part2(input);
-        assert_eq!(part1, "157");
-        assert_eq!(part2, "70");
+        assert_eq!(part1, Answer::Int(157));
+        assert_eq!(part2, Answer::Int(70));
     }
-}
 
-bench_day!(03);
+    #[test]
+    fn test_day03_parse_errors() {
+        let err = Day03::parse("vJrwpWtwJgWrhcsFMMfFFhFp\nab1d\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.expected, "an item letter");
+        let err = Day03::parse("abcab\n").unwrap_err();
+        assert_eq!(err.expected, "an even number of items");
+        let err = Day03::parse("abcd\n").unwrap_err();
+        assert_eq!(err.expected, "compartments sharing an item");
+        let err = Day03::parse(&EXAMPLE.lines().take(5).collect::<Vec<_>>().join("\n")).unwrap_err();
+        assert_eq!(err.expected, "rucksacks in groups of three");
+        let err = Day03::parse("aa\nbb\ncc\n").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+        assert_eq!(err.expected, "a group of rucksacks sharing a badge");
+    }
+}
@@ -1,4 +1,8 @@
-use crate::day::Day;
+use crate::{
+    answer::Answer,
+    day::Day,
+    parse::{ParseError, Source},
+};
 
 pub struct Day04;
 
@@ -7,36 +11,40 @@ impl<'a> Day<'a> for Day04 {
     type Input = Vec<((i32, i32), (i32, i32))>;
     type ProcessedInput = Vec<((i32, i32), (i32, i32))>;
 
-    fn parse(input: &'a str) -> Self::Input {
+    fn parse(input: &'a str) -> Result<Self::Input, ParseError> {
+        let src = Source::new(Self::DAY, input);
+        let range = |s| {
+            let (l, r) = src.split_once(s, "-")?;
+            Ok((src.number(l)?, src.number(r)?))
+        };
         input
             .lines()
             .map(|line| {
-                let (e1, e2) = line.trim().split_once(',').unwrap();
-                let (e1l, e1r) = e1.split_once('-').unwrap();
-                let (e2l, e2r) = e2.split_once('-').unwrap();
-                (
-                    (e1l.parse().unwrap(), e1r.parse().unwrap()),
-                    (e2l.parse().unwrap(), e2r.parse().unwrap()),
-                )
+                let (e1, e2) = src.split_once(line.trim(), ",")?;
+                Ok((range(e1)?, range(e2)?))
             })
             .collect()
     }
 
-    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, String) {
+    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, Answer) {
         let ans = input
             .iter()
             .filter(|((e1l, e1r), (e2l, e2r))| (e1l - e2l) * (e1r - e2r) <= 0)
             .count()
-            .to_string();
+            .into();
         (input, ans)
     }
 
-    fn solve_part2(input: Self::ProcessedInput) -> String {
+    fn process(input: Self::Input) -> Self::ProcessedInput {
+        input
+    }
+
+    fn solve_part2(input: Self::ProcessedInput) -> Answer {
         input
             .iter()
             .filter(|((e1l, e1r), (e2l, e2r))| e1r >= e2l && e1l <= e2r)
             .count()
-            .to_string()
+            .into()
     }
 }
 
@@ -56,12 +64,19 @@ mod test_day04 {
 
     #[test]
     fn test_day04_examples() {
-        let input = Day04::parse(EXAMPLE);
+        let input = Day04::parse(EXAMPLE).unwrap();
         let (input, part1) = Day04::solve_part1(input);
         let part2 = Day04::solve_part2(input);
-        assert_eq!(part1, "2");
-        assert_eq!(part2, "4");
+        assert_eq!(part1, Answer::Int(2));
+        assert_eq!(part2, Answer::Int(4));
     }
-}
 
-bench_day!(04);
+    #[test]
+    fn test_day04_parse_errors() {
+        let err = Day04::parse("2-4,6-8\n2-3;4-5\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 8));
+        assert_eq!(err.expected, "\",\"");
+        let err = Day04::parse("2-4,6-x\n").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 7));
+    }
+}
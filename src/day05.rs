@@ -1,4 +1,8 @@
-use crate::day::Day;
+use crate::{
+    answer::Answer,
+    day::Day,
+    parse::{end_of, ParseError, Source},
+};
 
 pub struct Day05;
 
@@ -7,30 +11,53 @@ impl<'a> Day<'a> for Day05 {
     type Input = (Vec<Vec<u8>>, Vec<(usize, usize, usize)>);
     type ProcessedInput = (Vec<Vec<u8>>, Vec<(usize, usize, usize)>);
 
-    fn parse(input: &'a str) -> Self::Input {
-        let (stacks, moves) = input.split_once("\n\n").unwrap();
+    fn parse(input: &'a str) -> Result<Self::Input, ParseError> {
+        let src = Source::new(Self::DAY, input);
+        let (stacks, moves) = input
+            .split_once("\n\n")
+            .ok_or_else(|| src.error(end_of(input), "a blank line after the stacks"))?;
         let rows = stacks.lines().rev().collect::<Vec<_>>();
-        let stacks = (0..rows[0].len() / 4 + 1)
+        let labels = rows
+            .first()
+            .ok_or_else(|| src.error(stacks, "a drawing of the stacks"))?;
+        let stacks = (0..labels.len() / 4 + 1)
             .map(|s| {
                 rows.iter()
                     .skip(1)
-                    .map(|r| r.as_bytes()[4 * s + 1])
+                    .map(|r| r.as_bytes().get(4 * s + 1).copied().unwrap_or(b' '))
                     .take_while(u8::is_ascii_uppercase)
                     .collect()
             })
-            .collect();
+            .collect::<Vec<Vec<_>>>();
+        let stack = |s| match src.number::<usize>(s)? {
+            n if (1..=stacks.len()).contains(&n) => Ok(n - 1),
+            _ => Err(src.error(s, format!("a stack from 1 to {}", stacks.len()))),
+        };
+        let mut heights = stacks.iter().map(Vec::len).collect::<Vec<_>>();
         let moves = moves
             .lines()
             .map(|line| {
-                let mut nums = line.split_whitespace().skip(1).step_by(2);
-                let mut num = || nums.next().unwrap().parse::<usize>().unwrap();
-                (num(), num() - 1, num() - 1)
+                let (count, rest) = src.split_once(src.strip_prefix(line, "move ")?, " from ")?;
+                let (from, to) = src.split_once(rest, " to ")?;
+                let (n, from, to) = (src.number(count)?, stack(from)?, stack(to)?);
+                if n > heights[from] {
+                    let expected = format!("at most the {} crate(s) on the stack", heights[from]);
+                    return Err(src.error(count, expected));
+                }
+                heights[from] -= n;
+                heights[to] += n;
+                Ok((n, from, to))
             })
-            .collect();
-        (stacks, moves)
+            .collect::<Result<_, _>>()?;
+        // the answer is the top crate of every stack
+        if let Some(empty) = heights.iter().position(|&height| height == 0) {
+            let expected = format!("a crate left on stack {}", empty + 1);
+            return Err(src.error(end_of(input), expected));
+        }
+        Ok((stacks, moves))
     }
 
-    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, String) {
+    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, Answer) {
         let mut stacks = input.0.clone();
         for (count, from, to) in input.1.iter().copied() {
             let cut_index = stacks[from].len() - count;
@@ -41,7 +68,11 @@ impl<'a> Day<'a> for Day05 {
         (input, ans)
     }
 
-    fn solve_part2((mut stacks, moves): Self::ProcessedInput) -> String {
+    fn process(input: Self::Input) -> Self::ProcessedInput {
+        input
+    }
+
+    fn solve_part2((mut stacks, moves): Self::ProcessedInput) -> Answer {
         for (count, from, to) in moves.iter().copied() {
             let cut_index = stacks[from].len() - count;
             let moved = stacks[from].split_off(cut_index);
@@ -70,12 +101,24 @@ mod test_day05 {
 
     #[test]
     fn test_day05_examples() {
-        let input = Day05::parse(EXAMPLE);
+        let input = Day05::parse(EXAMPLE).unwrap();
         let (input, part1) = Day05::solve_part1(input);
         let part2 = Day05::solve_part2(input);
-        assert_eq!(part1, "CMZ");
-        assert_eq!(part2, "MCD");
+        assert_eq!(part1, Answer::from("CMZ"));
+        assert_eq!(part2, Answer::from("MCD"));
     }
-}
 
-bench_day!(05);
+    #[test]
+    fn test_day05_parse_errors() {
+        let err = Day05::parse("[A]\n 1 \nmove 1 from 1 to 1\n").unwrap_err();
+        assert_eq!(err.expected, "a blank line after the stacks");
+        let err = Day05::parse("[A]\n 1 \n\nmove 1 from 2 to 1\n").unwrap_err();
+        assert_eq!((err.line, err.column), (4, 13));
+        assert_eq!(err.expected, "a stack from 1 to 1");
+        let err = Day05::parse("[A]\n 1 \n\nmove 2 from 1 to 1\n").unwrap_err();
+        assert_eq!((err.line, err.column), (4, 6));
+        assert_eq!(err.expected, "at most the 1 crate(s) on the stack");
+        let err = Day05::parse("[A]    \n 1   2 \n\nmove 1 from 1 to 2\n").unwrap_err();
+        assert_eq!(err.expected, "a crate left on stack 1");
+    }
+}
@@ -1,4 +1,8 @@
-use crate::day::Day;
+use crate::{
+    answer::Answer,
+    day::Day,
+    parse::{end_of, ParseError, Source},
+};
 
 pub struct Day06;
 
@@ -7,23 +11,33 @@ impl<'a> Day<'a> for Day06 {
     type Input = &'a str;
     type ProcessedInput = &'a str;
 
-    fn parse(input: &'a str) -> Self::Input {
-        input.trim()
+    fn parse(input: &'a str) -> Result<Self::Input, ParseError> {
+        let src = Source::new(Self::DAY, input);
+        let input = input.trim();
+        match input.find(|c: char| !c.is_ascii_lowercase()) {
+            Some(i) => Err(src.error(&input[i..], "a lowercase letter")),
+            None if input.len() < 4 => Err(src.error(end_of(input), "at least 4 characters")),
+            None => Ok(input),
+        }
     }
 
-    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, String) {
+    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, Answer) {
         let ans = (4..input.len())
             .find(|&i| (i - 4..i).all(|j| !input[i - 4..j].contains(&input[j..j + 1])))
             .unwrap()
-            .to_string();
+            .into();
         (input, ans)
     }
 
-    fn solve_part2(input: Self::ProcessedInput) -> String {
+    fn process(input: Self::Input) -> Self::ProcessedInput {
+        input
+    }
+
+    fn solve_part2(input: Self::ProcessedInput) -> Answer {
         (14..input.len())
             .find(|&i| (i - 14..i).all(|j| !input[i - 14..j].contains(&input[j..j + 1])))
             .unwrap()
-            .to_string()
+            .into()
     }
 }
 
@@ -39,21 +53,29 @@ mod test_day06 {
 
     #[test]
     fn test_day06_examples() {
-        assert_eq!(Day06::solve_part1(EXAMPLE1).1, "7");
-        assert_eq!(Day06::solve_part2(EXAMPLE1), "19");
+        assert_eq!(Day06::solve_part1(EXAMPLE1).1, Answer::Int(7));
+        assert_eq!(Day06::solve_part2(EXAMPLE1), Answer::Int(19));
 
-        assert_eq!(Day06::solve_part1(EXAMPLE2).1, "5");
-        assert_eq!(Day06::solve_part2(EXAMPLE2), "23");
+        assert_eq!(Day06::solve_part1(EXAMPLE2).1, Answer::Int(5));
+        assert_eq!(Day06::solve_part2(EXAMPLE2), Answer::Int(23));
 
-        assert_eq!(Day06::solve_part1(EXAMPLE3).1, "6");
-        assert_eq!(Day06::solve_part2(EXAMPLE3), "23");
+        assert_eq!(Day06::solve_part1(EXAMPLE3).1, Answer::Int(6));
+        assert_eq!(Day06::solve_part2(EXAMPLE3), Answer::Int(23));
 
-        assert_eq!(Day06::solve_part1(EXAMPLE4).1, "10");
-        assert_eq!(Day06::solve_part2(EXAMPLE4), "29");
+        assert_eq!(Day06::solve_part1(EXAMPLE4).1, Answer::Int(10));
+        assert_eq!(Day06::solve_part2(EXAMPLE4), Answer::Int(29));
 
-        assert_eq!(Day06::solve_part1(EXAMPLE5).1, "11");
-        assert_eq!(Day06::solve_part2(EXAMPLE5), "26");
+        assert_eq!(Day06::solve_part1(EXAMPLE5).1, Answer::Int(11));
+        assert_eq!(Day06::solve_part2(EXAMPLE5), Answer::Int(26));
     }
-}
 
-bench_day!(06);
+    #[test]
+    fn test_day06_parse_errors() {
+        let err = Day06::parse("mjqjpqmgbl5sphdz\n").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 11));
+        assert_eq!(err.expected, "a lowercase letter");
+        let err = Day06::parse("mjq\n").unwrap_err();
+        assert_eq!(err.expected, "at least 4 characters");
+        assert_eq!(Day06::solve_part1(Day06::parse("mjqpx\n").unwrap()).1, Answer::Int(4));
+    }
+}
@@ -1,5 +1,7 @@
 use crate::{
+    answer::Answer,
     day::Day,
+    parse::{ParseError, Source},
     util::{Tree, TreeZipper},
 };
 
@@ -12,43 +14,61 @@ impl<'a> Day<'a> for Day07 {
     type Input = Directory<'a>;
     type ProcessedInput = Vec<usize>;
 
-    fn parse(input: &'a str) -> Self::Input {
+    fn parse(input: &'a str) -> Result<Self::Input, ParseError> {
+        let src = Source::new(Self::DAY, input);
         let mut cwd = TreeZipper::new(Directory::default());
+        let mut total = 0;
         for line in input.lines() {
-            if line.starts_with("$ c") {
-                match &line[5..] {
+            if let Some(dir) = line.strip_prefix("$ cd ") {
+                match dir {
+                    ".." if cwd.parent.is_none() => {
+                        return Err(src.error(dir, "a directory below the root"))
+                    }
                     ".." => cwd.pop(),
                     dir => cwd.push(dir),
                 }
             } else if line.starts_with(|c: char| c.is_ascii_digit()) {
-                cwd.cursor.value += line.split_once(' ').unwrap().0.parse::<usize>().unwrap();
+                let digits = src.split_once(line, " ")?.0;
+                let size = src.number::<usize>(digits)?;
+                cwd.cursor.value += size;
+                total += size;
+                if total > 70000000 {
+                    return Err(src.error(digits, "files fitting on a 70000000 byte disk"));
+                }
+            } else if line != "$ ls" && !line.starts_with("dir ") {
+                return Err(src.error(line, "a command or directory listing"));
             }
         }
-        cwd.root()
+        Ok(cwd.root())
     }
 
-    fn solve_part1(file_system: Self::Input) -> (Self::ProcessedInput, String) {
-        let mut sizes = Vec::new();
-        file_system.fold(0, &mut |n, m| n + m, &mut |s, fs| {
-            sizes.push(s + fs);
-            s + fs
-        });
+    fn solve_part1(file_system: Self::Input) -> (Self::ProcessedInput, Answer) {
+        let sizes = Self::process(file_system);
         let ans = sizes
             .iter()
             .filter(|&&s| s <= 100000)
             .sum::<usize>()
-            .to_string();
+            .into();
         (sizes, ans)
     }
 
-    fn solve_part2(sizes: Self::ProcessedInput) -> String {
+    fn process(file_system: Self::Input) -> Self::ProcessedInput {
+        let mut sizes = Vec::new();
+        file_system.fold(0, &mut |n, m| n + m, &mut |s, fs| {
+            sizes.push(s + fs);
+            s + fs
+        });
+        sizes
+    }
+
+    fn solve_part2(sizes: Self::ProcessedInput) -> Answer {
         let required = 30000000 - (70000000 - sizes.last().unwrap());
         sizes
             .into_iter()
             .filter(|&s| s >= required)
             .min()
             .unwrap()
-            .to_string()
+            .into()
     }
 }
 
@@ -85,12 +105,23 @@ mod test_day07 {
 
     #[test]
     fn test_day07_examples() {
-        let input = Day07::parse(EXAMPLE);
+        let input = Day07::parse(EXAMPLE).unwrap();
         let (input, part1) = Day07::solve_part1(input);
         let part2 = Day07::solve_part2(input);
-        assert_eq!(part1, "95437");
-        assert_eq!(part2, "24933642");
+        assert_eq!(part1, Answer::Int(95437));
+        assert_eq!(part2, Answer::Int(24933642));
     }
-}
 
-bench_day!(07);
+    #[test]
+    fn test_day07_parse_errors() {
+        let err = Day07::parse("$ cd a\n$ cd ..\n$ cd ..\n").err().unwrap();
+        assert_eq!((err.line, err.column), (3, 6));
+        assert_eq!(err.expected, "a directory below the root");
+        let err = Day07::parse("$ cd /\n$ ls\nfile 123\n").err().unwrap();
+        assert_eq!((err.line, err.column), (3, 1));
+        assert_eq!(err.expected, "a command or directory listing");
+        let err = Day07::parse("$ cd /\n$ ls\n40000000 a\n40000000 b\n").err().unwrap();
+        assert_eq!((err.line, err.column), (4, 1));
+        assert_eq!(err.expected, "files fitting on a 70000000 byte disk");
+    }
+}
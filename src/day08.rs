@@ -1,4 +1,8 @@
-use crate::day::Day;
+use crate::{
+    answer::Answer,
+    day::Day,
+    parse::{end_of, ParseError, Source},
+};
 
 fn scan<I: Iterator<Item = usize>>(ray: I, grid: &[u32], vis_score: &mut [(bool, u32)]) {
     let mut seen = [None; 10];
@@ -17,14 +21,33 @@ impl<'a> Day<'a> for Day08 {
     type Input = (usize, usize, Vec<u32>); // (width, height, grid)
     type ProcessedInput = Vec<(bool, u32)>; // [(visible, score)]
 
-    fn parse(input: &'a str) -> Self::Input {
-        let width = input.find(|c: char| c.is_whitespace()).unwrap();
-        let grid = input.chars().filter_map(|c| c.to_digit(10)).collect();
-        let height = Vec::len(&grid) / width;
-        (width, height, grid)
+    fn parse(input: &'a str) -> Result<Self::Input, ParseError> {
+        let src = Source::new(Self::DAY, input);
+        let width = input.lines().next().map_or(0, str::len);
+        if width == 0 {
+            return Err(src.error(input, "a row of trees"));
+        }
+        let mut grid = Vec::new();
+        for line in input.lines() {
+            if let Some(i) = line.find(|c: char| !c.is_ascii_digit()) {
+                return Err(src.error(&line[i..], "a tree height"));
+            } else if line.len() != width {
+                let rest = line.get(width..).unwrap_or(end_of(line));
+                return Err(src.error(rest, format!("a row of {} trees", width)));
+            }
+            grid.extend(line.chars().filter_map(|c| c.to_digit(10)));
+        }
+        let height = grid.len() / width;
+        Ok((width, height, grid))
+    }
+
+    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, Answer) {
+        let vis_score = Self::process(input);
+        let visible = vis_score.iter().filter(|&&(v, _)| v).count();
+        (vis_score, visible.into())
     }
 
-    fn solve_part1((width, height, grid): Self::Input) -> (Self::ProcessedInput, String) {
+    fn process((width, height, grid): Self::Input) -> Self::ProcessedInput {
         let mut vis_score = vec![(false, 1); grid.len()];
         for j in 0..width {
             let ran = (0..height).map(|i| j + width * i);
@@ -36,12 +59,11 @@ impl<'a> Day<'a> for Day08 {
             scan(ran.clone().rev(), &grid, &mut vis_score);
             scan(ran, &grid, &mut vis_score);
         }
-        let visible = vis_score.iter().filter(|&&(v, _)| v).count();
-        (vis_score, visible.to_string())
+        vis_score
     }
 
-    fn solve_part2(vis_score: Self::ProcessedInput) -> String {
-        vis_score.iter().map(|&(_, s)| s).max().unwrap().to_string()
+    fn solve_part2(vis_score: Self::ProcessedInput) -> Answer {
+        vis_score.iter().map(|&(_, s)| s).max().unwrap().into()
     }
 }
 
@@ -60,12 +82,21 @@ mod test_day08 {
 
     #[test]
     fn test_day08_examples() {
-        let input = Day08::parse(EXAMPLE);
+        let input = Day08::parse(EXAMPLE).unwrap();
         let (input, part1) = Day08::solve_part1(input);
         let part2 = Day08::solve_part2(input);
-        assert_eq!(part1, "21");
-        assert_eq!(part2, "8");
+        assert_eq!(part1, Answer::Int(21));
+        assert_eq!(part2, Answer::Int(8));
     }
-}
 
-bench_day!(08);
+    #[test]
+    fn test_day08_parse_errors() {
+        let err = Day08::parse("303\n2x5\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.expected, "a tree height");
+        let err = Day08::parse("303\n25\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.expected, "a row of 3 trees");
+        assert_eq!(Day08::parse("").unwrap_err().expected, "a row of trees");
+    }
+}
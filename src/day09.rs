@@ -1,6 +1,10 @@
 use hashbrown::HashSet;
 
-use crate::day::Day;
+use crate::{
+    answer::Answer,
+    day::Day,
+    parse::{ParseError, Source},
+};
 
 fn tail_visits<const N: usize>(moves: &[((i32, i32), u32)]) -> usize {
     let mut rope = [(0, 0); N];
@@ -30,31 +34,36 @@ impl<'a> Day<'a> for Day09 {
     type Input = Vec<((i32, i32), u32)>;
     type ProcessedInput = Vec<((i32, i32), u32)>;
 
-    fn parse(input: &'a str) -> Self::Input {
+    fn parse(input: &'a str) -> Result<Self::Input, ParseError> {
+        let src = Source::new(Self::DAY, input);
         input
             .trim()
             .lines()
             .map(|line| {
-                let (dir, count) = line.split_once(' ').unwrap();
-                let count = count.parse().unwrap();
+                let (dir, count) = src.split_once(line, " ")?;
+                let count = src.number(count)?;
                 match dir {
-                    "U" => ((0, 1), count),
-                    "D" => ((0, -1), count),
-                    "L" => ((-1, 0), count),
-                    "R" => ((1, 0), count),
-                    _ => panic!("unknown direction"),
+                    "U" => Ok(((0, 1), count)),
+                    "D" => Ok(((0, -1), count)),
+                    "L" => Ok(((-1, 0), count)),
+                    "R" => Ok(((1, 0), count)),
+                    _ => Err(src.error(dir, "U, D, L or R")),
                 }
             })
             .collect()
     }
 
-    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, String) {
-        let ans = tail_visits::<2>(&input).to_string();
+    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, Answer) {
+        let ans = tail_visits::<2>(&input).into();
         (input, ans)
     }
 
-    fn solve_part2(input: Self::ProcessedInput) -> String {
-        tail_visits::<10>(&input).to_string()
+    fn process(input: Self::Input) -> Self::ProcessedInput {
+        input
+    }
+
+    fn solve_part2(input: Self::ProcessedInput) -> Answer {
+        tail_visits::<10>(&input).into()
     }
 }
 
@@ -87,14 +96,22 @@ mod test_day09 {
 
     #[test]
     fn test_day09_examples() {
-        let input = Day09::parse(EXAMPLE1);
+        let input = Day09::parse(EXAMPLE1).unwrap();
         let (input, part1) = Day09::solve_part1(input);
         let part2 = Day09::solve_part2(input);
-        assert_eq!(part1, "13");
-        assert_eq!(part2, "1");
+        assert_eq!(part1, Answer::Int(13));
+        assert_eq!(part2, Answer::Int(1));
 
-        assert_eq!(Day09::solve_part2(Day09::parse(EXAMPLE2)), "36");
+        assert_eq!(Day09::solve_part2(Day09::parse(EXAMPLE2).unwrap()), Answer::Int(36));
     }
-}
 
-bench_day!(09);
+    #[test]
+    fn test_day09_parse_errors() {
+        let err = Day09::parse("R 4\nX 4\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.expected, "U, D, L or R");
+        let err = Day09::parse("R 4\nU four\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.expected, "a number");
+    }
+}
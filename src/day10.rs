@@ -1,4 +1,9 @@
-use crate::{day::Day, util};
+use crate::{
+    answer::Answer,
+    day::Day,
+    parse::{end_of, ParseError, Source},
+    util,
+};
 
 fn run<F: FnMut(i32, i32) -> bool>(mut program: &[Option<i32>], mut body: F) {
     let mut queue = None;
@@ -26,15 +31,24 @@ impl<'a> Day<'a> for Day10 {
     type Input = Vec<Option<i32>>;
     type ProcessedInput = Vec<Option<i32>>;
 
-    fn parse(input: &'a str) -> Self::Input {
-        input
+    fn parse(input: &'a str) -> Result<Self::Input, ParseError> {
+        let src = Source::new(Self::DAY, input);
+        let program = input
             .trim()
             .lines()
-            .map(|line| line.starts_with('a').then(|| line[5..].parse().unwrap()))
-            .collect()
+            .map(|line| match line.strip_prefix("addx ") {
+                Some(n) => src.number(n).map(Some),
+                None if line == "noop" => Ok(None),
+                None => Err(src.error(line, "noop or addx")),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        match program.is_empty() {
+            true => Err(src.error(end_of(input), "noop or addx")),
+            false => Ok(program),
+        }
     }
 
-    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, String) {
+    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, Answer) {
         let mut ans = 0;
         run(&input, |cycle, x| {
             if (cycle - 20) % 40 == 0 {
@@ -42,10 +56,14 @@ impl<'a> Day<'a> for Day10 {
             }
             cycle < 220
         });
-        (input, ans.to_string())
+        (input, ans.into())
+    }
+
+    fn process(input: Self::Input) -> Self::ProcessedInput {
+        input
     }
 
-    fn solve_part2(input: Self::ProcessedInput) -> String {
+    fn solve_part2(input: Self::ProcessedInput) -> Answer {
         let mut image = [[false; 40]; 6];
         run(&input, |cycle, x| {
             let (row, col) = ((cycle - 1) / 40, (cycle - 1) % 40);
@@ -55,7 +73,7 @@ impl<'a> Day<'a> for Day10 {
             true
         });
         (0..8)
-            .map(|i| util::decode4x6char(|x, y| image[y][5 * i + x]).unwrap())
+            .map(|i| util::decode4x6char(|x, y| image[y][5 * i + x]).unwrap_or('?'))
             .collect()
     }
 }
@@ -216,10 +234,17 @@ mod test_day10 {
 
     #[test]
     fn test_day10_examples() {
-        let input = Day10::parse(EXAMPLE);
+        let input = Day10::parse(EXAMPLE).unwrap();
         let (_, part1) = Day10::solve_part1(input);
-        assert_eq!(part1, "13140");
+        assert_eq!(part1, Answer::Int(13140));
     }
-}
 
-bench_day!(10);
+    #[test]
+    fn test_day10_parse_error() {
+        let err = Day10::parse("noop\naddx 3\nadd 5\n").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+        assert_eq!(err.expected, "noop or addx");
+        assert_eq!(err.found, "\"add 5\"");
+        assert_eq!(Day10::parse("\n").unwrap_err().expected, "noop or addx");
+    }
+}
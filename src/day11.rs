@@ -1,4 +1,8 @@
-use crate::day::Day;
+use crate::{
+    answer::Answer,
+    day::Day,
+    parse::{end_of, ParseError, Source},
+};
 
 #[derive(Clone)]
 enum Operation {
@@ -17,14 +21,14 @@ impl Operation {
     }
 }
 
-fn read_operation(string: &str) -> Operation {
-    assert!(string.starts_with("new = old "));
-    match (&string[10..12], &string[12..]) {
-        ("+ ", "old") => Operation::Mul(2),
-        ("* ", "old") => Operation::Square,
-        ("+ ", n) => Operation::Add(n.parse().unwrap()),
-        ("* ", n) => Operation::Mul(n.parse().unwrap()),
-        _ => panic!("couldn't read operation"),
+fn read_operation<'a>(src: Source<'a>, string: &'a str) -> Result<Operation, ParseError> {
+    let operation = src.strip_prefix(string, "new = old ")?;
+    match src.split_once(operation, " ")? {
+        ("+", "old") => Ok(Operation::Mul(2)),
+        ("*", "old") => Ok(Operation::Square),
+        ("+", n) => Ok(Operation::Add(src.number(n)?)),
+        ("*", n) => Ok(Operation::Mul(src.number(n)?)),
+        (op, _) => Err(src.error(op, "+ or *")),
     }
 }
 
@@ -67,35 +71,73 @@ impl<'a> Day<'a> for Day11 {
     type Input = Vec<Monkey>;
     type ProcessedInput = Vec<Monkey>;
 
-    fn parse(input: &'a str) -> Self::Input {
-        input
+    fn parse(input: &'a str) -> Result<Self::Input, ParseError> {
+        let src = Source::new(Self::DAY, input);
+        let monkeys = input
             .trim()
             .split("\n\n")
             .map(|chunk| {
-                let mut lines = chunk.lines().skip(1);
-                Monkey {
-                    queue: lines.next().unwrap()[18..]
-                        .split(", ")
-                        .map(|n| n.parse().unwrap())
-                        .collect(),
+                let mut lines = chunk.lines().map(str::trim_start);
+                let mut field = |prefix: &str| {
+                    let line = lines
+                        .next()
+                        .ok_or_else(|| src.error(end_of(chunk), format!("{:?}", prefix)))?;
+                    src.strip_prefix(line, prefix)
+                };
+                field("Monkey ")?;
+                let queue = field("Starting items: ")?
+                    .split(", ")
+                    .map(|n| src.number(n))
+                    .collect::<Result<_, _>>()?;
+                let operation = read_operation(src, field("Operation: ")?)?;
+                let test = field("Test: divisible by ")?;
+                let test_base = match src.number(test)? {
+                    0 => return Err(src.error(test, "a non-zero divisor")),
+                    n => n,
+                };
+                let targets = [
+                    field("If true: throw to monkey ")?,
+                    field("If false: throw to monkey ")?,
+                ];
+                let monkey = Monkey {
+                    queue,
                     inspections: 0,
-                    operation: read_operation(&lines.next().unwrap()[13..]),
-                    test_base: lines.next().unwrap()[21..].parse().unwrap(),
-                    divisible_target: lines.next().unwrap()[29..].parse().unwrap(),
-                    nondivisible_target: lines.next().unwrap()[30..].parse().unwrap(),
-                }
+                    operation,
+                    test_base,
+                    divisible_target: src.number(targets[0])?,
+                    nondivisible_target: src.number(targets[1])?,
+                };
+                Ok((monkey, targets))
             })
-            .collect()
+            .collect::<Result<Vec<_>, _>>()?;
+        for (monkey, targets) in monkeys.iter() {
+            let indices = [monkey.divisible_target, monkey.nondivisible_target];
+            if let Some((target, _)) = targets
+                .iter()
+                .zip(indices)
+                .find(|&(_, i)| i >= monkeys.len())
+            {
+                return Err(src.error(
+                    target,
+                    format!("a monkey numbered below {}", monkeys.len()),
+                ));
+            }
+        }
+        Ok(monkeys.into_iter().map(|(monkey, _)| monkey).collect())
     }
 
-    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, String) {
+    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, Answer) {
         let mut monkeys = input.clone();
-        let ans = monkey_business(&mut monkeys, 20, true).to_string();
+        let ans = monkey_business(&mut monkeys, 20, true).into();
         (input, ans)
     }
 
-    fn solve_part2(mut monkeys: Self::ProcessedInput) -> String {
-        monkey_business(&mut monkeys, 10000, false).to_string()
+    fn process(input: Self::Input) -> Self::ProcessedInput {
+        input
+    }
+
+    fn solve_part2(mut monkeys: Self::ProcessedInput) -> Answer {
+        monkey_business(&mut monkeys, 10000, false).into()
     }
 }
 
@@ -136,12 +178,30 @@ mod test_day11 {
 
     #[test]
     fn test_day11_examples() {
-        let input = Day11::parse(EXAMPLE);
+        let input = Day11::parse(EXAMPLE).unwrap();
         let (input, part1) = Day11::solve_part1(input);
         let part2 = Day11::solve_part2(input);
-        assert_eq!(part1, "10605");
-        assert_eq!(part2, "2713310158");
+        assert_eq!(part1, Answer::Int(10605));
+        assert_eq!(part2, Answer::Int(2713310158));
     }
-}
 
-bench_day!(11);
+    #[test]
+    fn test_day11_parse_errors() {
+        let monkey = |test, target| {
+            format!(
+                "Monkey 0:\n  Starting items: 79\n  Operation: new = old * 19\n  \
+                Test: divisible by {}\n    If true: throw to monkey {}\n    \
+                If false: throw to monkey 0\n",
+                test, target
+            )
+        };
+        assert!(Day11::parse(&monkey(23, 0)).is_ok());
+        let err = Day11::parse(&monkey(23, 7)).err().unwrap();
+        assert_eq!((err.line, err.column), (5, 30));
+        assert_eq!(err.expected, "a monkey numbered below 1");
+        let err = Day11::parse(&monkey(0, 0)).err().unwrap();
+        assert_eq!((err.line, err.column), (4, 22));
+        assert!(Day11::parse(&EXAMPLE[..EXAMPLE.len() - 30]).is_err());
+        assert!(Day11::parse("").is_err());
+    }
+}
@@ -1,5 +1,11 @@
-use crate::{day::Day, util};
+use crate::{
+    answer::Answer,
+    day::Day,
+    parse::{end_of, ParseError, Source},
+    util,
+};
 
+#[derive(Clone)]
 pub struct Input {
     heights: Vec<i32>,
     width: usize,
@@ -33,55 +39,69 @@ impl<'a> Day<'a> for Day12 {
     type Input = Input;
     type ProcessedInput = Input;
 
-    fn parse(input: &'a str) -> Self::Input {
-        let width = input.find(|c: char| c.is_whitespace()).unwrap();
-        let (mut start, mut end) = ((0, 0), (0, 0));
+    fn parse(input: &'a str) -> Result<Self::Input, ParseError> {
+        let src = Source::new(Self::DAY, input);
+        let input = input.trim();
+        let width = input.lines().next().map_or(0, str::len);
+        if width == 0 {
+            return Err(src.error(input, "a heightmap"));
+        }
+        let (mut start, mut end) = (None, None);
         let mut heights = Vec::new();
-        for (i, line) in input.trim().lines().enumerate() {
+        for (i, line) in input.lines().enumerate() {
+            if line.len() != width {
+                let rest = line.get(width..).unwrap_or(end_of(line));
+                return Err(src.error(rest, format!("a row of {} squares", width)));
+            }
             for (j, c) in line.bytes().enumerate() {
                 let h = match c {
                     b'S' => {
-                        start = (j, i);
+                        start = Some((j, i));
                         0
                     }
                     b'E' => {
-                        end = (j, i);
+                        end = Some((j, i));
                         25
                     }
-                    c => c - b'a',
+                    b'a'..=b'z' => c - b'a',
+                    _ => return Err(src.error(&line[j..], "a height from a to z")),
                 };
                 heights.push(h as i32);
             }
         }
         let height = heights.len() / width;
-        Input {
+        Ok(Input {
             heights,
             width,
             height,
-            start,
-            end,
-        }
+            start: start.ok_or_else(|| src.error(end_of(input), "a start square S"))?,
+            end: end.ok_or_else(|| src.error(end_of(input), "an end square E"))?,
+        })
     }
 
-    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, String) {
+    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, Answer) {
         let ans = util::bfs(
             input.start,
             |pos| neighbours(pos, &input, |old_h, new_h| new_h - old_h <= 1),
             |pos| pos == input.end,
         )
         .unwrap()
-        .to_string();
+        .into();
         (input, ans)
     }
 
-    fn solve_part2(input: Self::ProcessedInput) -> String {
+    fn process(input: Self::Input) -> Self::ProcessedInput {
+        input
+    }
+
+    fn solve_part2(input: Self::ProcessedInput) -> Answer {
         util::bfs(
             input.end,
             |pos| neighbours(pos, &input, |old_h, new_h| old_h - new_h <= 1),
             |pos| input.heights[pos.0 + input.width * pos.1] == 0,
         )
         .unwrap()
-        .to_string()
+        .into()
     }
 }
 
@@ -100,12 +120,21 @@ mod test_day12 {
 
     #[test]
     fn test_day12_examples() {
-        let input = Day12::parse(EXAMPLE);
+        let input = Day12::parse(EXAMPLE).unwrap();
         let (input, part1) = Day12::solve_part1(input);
         let part2 = Day12::solve_part2(input);
-        assert_eq!(part1, "31");
-        assert_eq!(part2, "29");
+        assert_eq!(part1, Answer::Int(31));
+        assert_eq!(part2, Answer::Int(29));
     }
-}
 
-bench_day!(12);
+    #[test]
+    fn test_day12_parse_errors() {
+        let err = Day12::parse("Sab\nabE\nab\n").err().unwrap();
+        assert_eq!((err.line, err.column), (3, 3));
+        assert_eq!(err.expected, "a row of 3 squares");
+        let err = Day12::parse("Sab\nab!\n").err().unwrap();
+        assert_eq!((err.line, err.column), (2, 3));
+        let err = Day12::parse("Sab\nabc\n").err().unwrap();
+        assert_eq!(err.expected, "an end square E");
+    }
+}
@@ -1,29 +1,37 @@
 use std::cmp::Ordering;
 
-use crate::day::Day;
+use crate::{
+    answer::Answer,
+    day::Day,
+    parse::{end_of, ParseError, Source},
+};
 
+#[derive(Clone)]
 pub enum Packet {
     Num(i32),
     List(Vec<Packet>),
 }
 
-fn read_packet(mut string: &str) -> (Packet, &str) {
-    if string.starts_with('[') {
-        string = &string[1..];
+fn read_packet<'a>(src: Source<'a>, mut string: &'a str) -> Result<(Packet, &'a str), ParseError> {
+    if let Some(rest) = string.strip_prefix('[') {
+        string = rest;
         let mut list = Vec::new();
         while !string.starts_with(']') {
-            assert!(string.starts_with(',') || list.is_empty());
-            if string.starts_with(',') {
-                string = &string[1..];
+            if !list.is_empty() {
+                string = string
+                    .strip_prefix(',')
+                    .ok_or_else(|| src.error(string, "',' or ']'"))?;
             }
-            let (packet, rest) = read_packet(string);
+            let (packet, rest) = read_packet(src, string)?;
             list.push(packet);
             string = rest;
         }
-        (Packet::List(list), &string[1..])
+        Ok((Packet::List(list), &string[1..]))
     } else {
-        let end = string.find(|c: char| !c.is_ascii_digit()).unwrap();
-        (Packet::Num(string[..end].parse().unwrap()), &string[end..])
+        let end = string
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(string.len());
+        Ok((Packet::Num(src.number(&string[..end])?), &string[end..]))
     }
 }
 
@@ -51,31 +59,42 @@ impl<'a> Day<'a> for Day13 {
     type Input = Vec<[Packet; 2]>;
     type ProcessedInput = Vec<[Packet; 2]>;
 
-    fn parse(input: &'a str) -> Self::Input {
+    fn parse(input: &'a str) -> Result<Self::Input, ParseError> {
+        let src = Source::new(Self::DAY, input);
+        let packet = |line| match read_packet(src, line)? {
+            (packet, "") => Ok(packet),
+            (_, rest) => Err(src.error(rest, "end of packet")),
+        };
         input
             .trim()
             .split("\n\n")
             .map(|chunk| {
                 let mut lines = chunk.lines();
-                let fst = lines.next().unwrap();
-                let snd = lines.next().unwrap();
-                [read_packet(fst).0, read_packet(snd).0]
+                let mut next = || {
+                    let line = lines.next();
+                    line.ok_or_else(|| src.error(end_of(chunk), "another packet"))
+                };
+                Ok([packet(next()?)?, packet(next()?)?])
             })
             .collect()
     }
 
-    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, String) {
+    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, Answer) {
         let ans = input
             .iter()
             .enumerate()
             .filter(|(_, pair)| cmp(&pair[0], &pair[1]) == Ordering::Less)
             .map(|(i, _)| i + 1)
             .sum::<usize>()
-            .to_string();
+            .into();
         (input, ans)
     }
 
-    fn solve_part2(input: Self::ProcessedInput) -> String {
+    fn process(input: Self::Input) -> Self::ProcessedInput {
+        input
+    }
+
+    fn solve_part2(input: Self::ProcessedInput) -> Answer {
         let mut dividers = [(Packet::Num(2), 1), (Packet::Num(6), 2)];
         for packet in input.into_iter().flat_map(|a| a.into_iter()) {
             for (divider, count) in dividers.iter_mut() {
@@ -88,7 +107,7 @@ impl<'a> Day<'a> for Day13 {
             .into_iter()
             .map(|(_, count)| count)
             .product::<usize>()
-            .to_string()
+            .into()
     }
 }
 
@@ -125,12 +144,22 @@ mod test_day13 {
 
     #[test]
     fn test_day13_examples() {
-        let input = Day13::parse(EXAMPLE);
+        let input = Day13::parse(EXAMPLE).unwrap();
         let (input, part1) = Day13::solve_part1(input);
         let part2 = Day13::solve_part2(input);
-        assert_eq!(part1, "13");
-        assert_eq!(part2, "140");
+        assert_eq!(part1, Answer::Int(13));
+        assert_eq!(part2, Answer::Int(140));
     }
-}
 
-bench_day!(13);
+    #[test]
+    fn test_day13_parse_errors() {
+        let err = Day13::parse("[1,[2]]\n[1,2\n").err().unwrap();
+        assert_eq!((err.line, err.column), (2, 5));
+        assert_eq!(err.expected, "',' or ']'");
+        let err = Day13::parse("[1]\n[2]]\n").err().unwrap();
+        assert_eq!((err.line, err.column), (2, 4));
+        assert_eq!(err.expected, "end of packet");
+        let err = Day13::parse("[1]\n[2]\n\n[3]\n").err().unwrap();
+        assert_eq!(err.expected, "another packet");
+    }
+}
@@ -1,6 +1,10 @@
 use hashbrown::HashSet;
 
-use crate::day::Day;
+use crate::{
+    answer::Answer,
+    day::Day,
+    parse::{end_of, ParseError, Source},
+};
 
 fn drop_sand(grid: &mut HashSet<(i32, i32)>, ground: i32, use_ground: bool) -> Option<(i32, i32)> {
     let (mut sx, mut sy) = (500, 0);
@@ -41,16 +45,17 @@ impl<'a> Day<'a> for Day14 {
     type Input = HashSet<(i32, i32)>;
     type ProcessedInput = (i32, HashSet<(i32, i32)>);
 
-    fn parse(input: &'a str) -> Self::Input {
+    fn parse(input: &'a str) -> Result<Self::Input, ParseError> {
+        let src = Source::new(Self::DAY, input);
         let mut grid = HashSet::new();
         for line in input.trim().lines() {
             let points = line
                 .split(" -> ")
                 .map(|coord| {
-                    let (x, y) = coord.split_once(',').unwrap();
-                    (x.parse().unwrap(), y.parse().unwrap())
+                    let (x, y) = src.split_once(coord, ",")?;
+                    Ok((src.number(x)?, src.number(y)?))
                 })
-                .collect::<Vec<_>>();
+                .collect::<Result<Vec<_>, _>>()?;
             grid.extend(points.windows(2).flat_map(|wind| {
                 let (x0, y0) = wind[0];
                 let (x1, y1) = wind[1];
@@ -59,26 +64,34 @@ impl<'a> Day<'a> for Day14 {
                 (x0..=x1).flat_map(move |x| (y0..=y1).map(move |y| (x, y)))
             }));
         }
-        grid
+        match grid.is_empty() {
+            true => Err(src.error(end_of(input), "a path of rock")),
+            false => Ok(grid),
+        }
     }
 
-    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, String) {
+    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, Answer) {
+        let (ground, input) = Self::process(input);
         let mut grid = input.clone();
-        let ground = *grid.iter().map(|(_, y)| y).max().unwrap() + 2;
         let mut drops = 0;
         loop {
             match drop_sand(&mut grid, ground, false) {
                 Some(_) => drops += 1,
-                None => return ((ground, input), drops.to_string()),
+                None => return ((ground, input), drops.into()),
             }
         }
     }
 
-    fn solve_part2((ground, mut grid): Self::ProcessedInput) -> String {
+    fn process(grid: Self::Input) -> Self::ProcessedInput {
+        let ground = *grid.iter().map(|(_, y)| y).max().unwrap() + 2;
+        (ground, grid)
+    }
+
+    fn solve_part2((ground, mut grid): Self::ProcessedInput) -> Answer {
         let mut drops = 0;
         loop {
             match drop_sand(&mut grid, ground, true) {
-                Some((500, 0)) => return (drops + 1).to_string(),
+                Some((500, 0)) => return (drops + 1).into(),
                 Some(_) => drops += 1,
                 None => unreachable!(),
             }
@@ -98,12 +111,19 @@ mod test_day14 {
 
     #[test]
     fn test_day14_examples() {
-        let input = Day14::parse(EXAMPLE);
+        let input = Day14::parse(EXAMPLE).unwrap();
         let (input, part1) = Day14::solve_part1(input);
         let part2 = Day14::solve_part2(input);
-        assert_eq!(part1, "24");
-        assert_eq!(part2, "93");
+        assert_eq!(part1, Answer::Int(24));
+        assert_eq!(part2, Answer::Int(93));
     }
-}
 
-bench_day!(14);
+    #[test]
+    fn test_day14_parse_errors() {
+        let err = Day14::parse("498,4 -> 498,6\n503,4 -> 502\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 13));
+        assert_eq!(err.expected, "\",\"");
+        let err = Day14::parse("498,4\n").unwrap_err();
+        assert_eq!(err.expected, "a path of rock");
+    }
+}
@@ -3,7 +3,11 @@ use std::cmp::Ordering;
 use hashbrown::HashSet;
 use itertools::Itertools;
 
-use crate::day::Day;
+use crate::{
+    answer::Answer,
+    day::Day,
+    parse::{end_of, ParseError, Source},
+};
 
 fn merge(int1: (i32, i32), int2: (i32, i32)) -> Option<(i32, i32)> {
     (int1.1 >= int2.0 - 1 && int1.0 <= int2.1 + 1)
@@ -33,22 +37,31 @@ impl<'a, const ROW: i32> Day<'a> for Day15Generic<ROW> {
     type Input = Vec<Sensor>;
     type ProcessedInput = Vec<Sensor>;
 
-    fn parse(input: &'a str) -> Self::Input {
-        fn xy(s: &str) -> (i32, i32) {
-            let (x, y) = s.split_once(',').unwrap();
-            (x[2..].parse().unwrap(), y[3..].parse().unwrap())
-        }
-        input
+    fn parse(input: &'a str) -> Result<Self::Input, ParseError> {
+        let src = Source::new(Self::DAY, input);
+        let xy = |s| {
+            let (x, y) = src.split_once(s, ", ")?;
+            let x = src.number(src.strip_prefix(x, "x=")?)?;
+            let y = src.number(src.strip_prefix(y, "y=")?)?;
+            Ok((x, y))
+        };
+        let sensors = input
             .trim()
             .lines()
             .map(|line| {
-                let (sensor, beacon) = line.split_once(':').unwrap();
-                Sensor::new(xy(&sensor[10..]), xy(&beacon[22..]))
+                let (sensor, beacon) = src.split_once(line, ": ")?;
+                let sensor = xy(src.strip_prefix(sensor, "Sensor at ")?)?;
+                let beacon = xy(src.strip_prefix(beacon, "closest beacon is at ")?)?;
+                Ok(Sensor::new(sensor, beacon))
             })
-            .collect()
+            .collect::<Result<Vec<_>, _>>()?;
+        match sensors.is_empty() {
+            true => Err(src.error(end_of(input), "a sensor")),
+            false => Ok(sensors),
+        }
     }
 
-    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, String) {
+    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, Answer) {
         let intervals = merge_all(input.iter().filter_map(|s| s.slice(ROW)).collect());
         let obstructed = intervals.iter().map(|i| 1 + i.1 - i.0).sum::<i32>();
         let beacons = input
@@ -56,10 +69,14 @@ impl<'a, const ROW: i32> Day<'a> for Day15Generic<ROW> {
             .filter_map(|s| (s.beacon.1 == ROW).then_some(s.beacon.0))
             .collect::<HashSet<_>>()
             .len();
-        (input, (obstructed - beacons as i32).to_string())
+        (input, (obstructed - beacons as i32).into())
     }
 
-    fn solve_part2(input: Self::ProcessedInput) -> String {
+    fn process(input: Self::Input) -> Self::ProcessedInput {
+        input
+    }
+
+    fn solve_part2(input: Self::ProcessedInput) -> Answer {
         let (x, y) = input
             .iter()
             .tuple_combinations()
@@ -73,7 +90,7 @@ impl<'a, const ROW: i32> Day<'a> for Day15Generic<ROW> {
                     .flatten()
             })
             .unwrap();
-        (x as u64 * 4000000 + y as u64).to_string()
+        (x as u64 * 4000000 + y as u64).into()
     }
 }
 
@@ -144,12 +161,20 @@ mod test_day15 {
 
     #[test]
     fn test_day15_examples() {
-        let input = Day15Generic::<10>::parse(EXAMPLE);
+        let input = Day15Generic::<10>::parse(EXAMPLE).unwrap();
         let (input, part1) = Day15Generic::<10>::solve_part1(input);
         let part2 = Day15Generic::<10>::solve_part2(input);
-        assert_eq!(part1, "26");
-        assert_eq!(part2, "56000011");
+        assert_eq!(part1, Answer::Int(26));
+        assert_eq!(part2, Answer::Int(56000011));
     }
-}
 
-bench_day!(15);
+    #[test]
+    fn test_day15_parse_errors() {
+        let err = Day15::parse("Sensor at x=2, y=18: closest beacon at x=-2, y=15\n").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 22));
+        assert_eq!(err.expected, "\"closest beacon is at \"");
+        let err = Day15::parse("Sensor at x=2, y=z: closest beacon is at x=-2, y=15\n").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 18));
+        assert_eq!(Day15::parse("\n").unwrap_err().expected, "a sensor");
+    }
+}
@@ -3,7 +3,11 @@ use std::collections::VecDeque;
 use hashbrown::HashMap;
 use itertools::iproduct;
 
-use crate::day::Day;
+use crate::{
+    answer::Answer,
+    day::Day,
+    parse::{end_of, ParseError, Source},
+};
 
 fn get_distances<'a>(
     valves: &HashMap<&'a str, (i32, Vec<&'a str>)>,
@@ -25,6 +29,7 @@ fn get_distances<'a>(
     distances
 }
 
+#[derive(Clone)]
 pub struct Volcano {
     start: u32,
     valves: Vec<(u32, i32)>,
@@ -55,7 +60,7 @@ fn max_releases(time: i32, volcano: &Volcano) -> HashMap<u32, i32> {
             let new_open = open | bit;
             let new_released = released + flow * new_time;
             if new_time > 0
-                && releases.get(&new_open).map_or(true, |&r| {
+                && releases.get(&new_open).is_none_or(|&r| {
                     r < new_released + potential(new_time, new_open, &volcano.valves)
                 })
             {
@@ -73,18 +78,29 @@ impl<'a> Day<'a> for Day16 {
     type Input = Volcano;
     type ProcessedInput = Volcano;
 
-    fn parse(input: &'a str) -> Self::Input {
+    fn parse(input: &'a str) -> Result<Self::Input, ParseError> {
+        let src = Source::new(Self::DAY, input);
         let graph = input
             .trim()
             .lines()
             .map(|line| {
-                let (valve, tunnels) = line.split_once(';').unwrap();
-                let flow = valve[23..].parse().unwrap();
-                let s = tunnels.find(|c: char| c.is_ascii_uppercase()).unwrap();
-                let tunnels = tunnels[s..].split(", ").collect();
-                (&valve[6..8], (flow, tunnels))
+                let (valve, tunnels) = src.split_once(line, "; ")?;
+                let valve = src.strip_prefix(valve, "Valve ")?;
+                let (valve, flow) = src.split_once(valve, " has flow rate=")?;
+                let tunnels = ["tunnels lead to valves ", "tunnel leads to valve "]
+                    .iter()
+                    .find_map(|prefix| tunnels.strip_prefix(prefix))
+                    .ok_or_else(|| src.error(tunnels, "a list of tunnels"))?;
+                Ok((valve, (src.number(flow)?, tunnels.split(", ").collect())))
             })
-            .collect::<HashMap<_, _>>();
+            .collect::<Result<HashMap<_, (i32, Vec<_>)>, _>>()?;
+        let unknown = graph.values().flat_map(|v| v.1.iter());
+        if let Some(tunnel) = unknown.clone().find(|t| !graph.contains_key(*t)) {
+            return Err(src.error(tunnel, "a known valve"));
+        }
+        if !graph.contains_key("AA") {
+            return Err(src.error(end_of(input), "a valve named AA"));
+        }
         let distances = get_distances(&graph);
         let valves = graph
             .iter()
@@ -93,9 +109,8 @@ impl<'a> Day<'a> for Day16 {
         let flag = |valve| valves.iter().position(|&v| v == valve).unwrap() as u32;
         let distances = distances
             .into_iter()
-            .filter_map(|((k1, k2), d)| {
-                (valves.contains(&k1) && valves.contains(&k2)).then(|| ((flag(k1), flag(k2)), d))
-            })
+            .filter(|((k1, k2), _)| valves.contains(k1) && valves.contains(k2))
+            .map(|((k1, k2), d)| ((flag(k1), flag(k2)), d))
             .collect();
         let mut valves = valves
             .iter()
@@ -103,29 +118,33 @@ impl<'a> Day<'a> for Day16 {
             .collect::<Vec<_>>();
         valves.sort_by_key(|&(_, f)| -f);
         let start = flag("AA");
-        Volcano {
+        Ok(Volcano {
             start,
             valves,
             distances,
-        }
+        })
     }
 
-    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, String) {
+    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, Answer) {
         let ans = max_releases(30, &input)
             .into_values()
             .max()
             .unwrap()
-            .to_string();
+            .into();
         (input, ans)
     }
 
-    fn solve_part2(input: Self::ProcessedInput) -> String {
+    fn process(input: Self::Input) -> Self::ProcessedInput {
+        input
+    }
+
+    fn solve_part2(input: Self::ProcessedInput) -> Answer {
         let releases = max_releases(26, &input);
         iproduct!(releases.iter(), releases.iter())
             .filter_map(|((o1, r1), (o2, r2))| ((o1 & o2) == 0).then_some(r1 + r2))
             .max()
             .unwrap()
-            .to_string()
+            .into()
     }
 }
 
@@ -149,12 +168,21 @@ mod test_day16 {
 
     #[test]
     fn test_day16_examples() {
-        let input = Day16::parse(EXAMPLE);
+        let input = Day16::parse(EXAMPLE).unwrap();
         let (input, part1) = Day16::solve_part1(input);
         let part2 = Day16::solve_part2(input);
-        assert_eq!(part1, "1651");
-        assert_eq!(part2, "1707");
+        assert_eq!(part1, Answer::Int(1651));
+        assert_eq!(part2, Answer::Int(1707));
     }
-}
 
-bench_day!(16);
+    #[test]
+    fn test_day16_parse_errors() {
+        let err = Day16::parse("Valve AA has flow rate=0; tunnels lead to valves BB\n").err().unwrap();
+        assert_eq!((err.line, err.column), (1, 50));
+        assert_eq!(err.expected, "a known valve");
+        let err = Day16::parse("Valve BB has flow rate=1; tunnel leads to valve BB\n").err().unwrap();
+        assert_eq!(err.expected, "a valve named AA");
+        let err = Day16::parse("Valve AA has flow rate=x; tunnel leads to valve AA\n").err().unwrap();
+        assert_eq!((err.line, err.column), (1, 24));
+    }
+}
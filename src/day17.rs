@@ -1,7 +1,11 @@
 use hashbrown::HashMap;
 use lazy_static::lazy_static;
 
-use crate::day::Day;
+use crate::{
+    answer::Answer,
+    day::Day,
+    parse::{end_of, ParseError, Source},
+};
 
 struct Rock {
     points: Vec<(i32, i32)>,
@@ -16,6 +20,7 @@ struct Placement {
     level: i32,
 }
 
+#[derive(Clone)]
 pub struct Cycle {
     start: usize,
     period: usize,
@@ -69,7 +74,7 @@ fn find_cycle(jets: &[i32]) -> Cycle {
             starts.retain(|p: &Placement| support.iter().all(|&i| i >= p.idx));
         }
         if (level - rock.height + 1..level)
-            .all(|y| (0..7).all(|x| tower.get(&(x, y)).map_or(true, |&i| i == idx)))
+            .all(|y| (0..7).all(|x| tower.get(&(x, y)).is_none_or(|&i| i == idx)))
         {
             if let Some(p) = starts
                 .iter()
@@ -130,22 +135,33 @@ impl<'a> Day<'a> for Day17 {
     type Input = Vec<i32>;
     type ProcessedInput = (Cycle, Vec<i32>);
 
-    fn parse(input: &'a str) -> Self::Input {
-        input
-            .trim()
-            .chars()
-            .map(|c| if c == '<' { -1 } else { 1 })
+    fn parse(input: &'a str) -> Result<Self::Input, ParseError> {
+        let src = Source::new(Self::DAY, input);
+        let jets = input.trim();
+        if jets.is_empty() {
+            return Err(src.error(end_of(input), "< or >"));
+        }
+        jets.char_indices()
+            .map(|(i, c)| match c {
+                '<' => Ok(-1),
+                '>' => Ok(1),
+                _ => Err(src.error(&jets[i..], "< or >")),
+            })
             .collect()
     }
 
-    fn solve_part1(jets: Self::Input) -> (Self::ProcessedInput, String) {
-        let cycle = find_cycle(&jets);
-        let ans = height_after(2022, &cycle, &jets).to_string();
+    fn solve_part1(jets: Self::Input) -> (Self::ProcessedInput, Answer) {
+        let (cycle, jets) = Self::process(jets);
+        let ans = height_after(2022, &cycle, &jets).into();
         ((cycle, jets), ans)
     }
 
-    fn solve_part2((cycle, jets): Self::ProcessedInput) -> String {
-        height_after(1000000000000, &cycle, &jets).to_string()
+    fn process(jets: Self::Input) -> Self::ProcessedInput {
+        (find_cycle(&jets), jets)
+    }
+
+    fn solve_part2((cycle, jets): Self::ProcessedInput) -> Answer {
+        height_after(1000000000000, &cycle, &jets).into()
     }
 }
 
@@ -157,12 +173,17 @@ mod test_day17 {
 
     #[test]
     fn test_day17_examples() {
-        let input = Day17::parse(EXAMPLE);
+        let input = Day17::parse(EXAMPLE).unwrap();
         let (input, part1) = Day17::solve_part1(input);
         let part2 = Day17::solve_part2(input);
-        assert_eq!(part1, "3068");
-        assert_eq!(part2, "1514285714288");
+        assert_eq!(part1, Answer::Int(3068));
+        assert_eq!(part2, Answer::Int(1514285714288));
     }
-}
 
-bench_day!(17);
+    #[test]
+    fn test_day17_parse_errors() {
+        let err = Day17::parse(">><x<\n").err().unwrap();
+        assert_eq!((err.line, err.column), (1, 4));
+        assert_eq!(Day17::parse("\n").err().unwrap().expected, "< or >");
+    }
+}
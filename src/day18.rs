@@ -3,7 +3,11 @@ use std::collections::VecDeque;
 use hashbrown::HashSet;
 use itertools::Itertools;
 
-use crate::day::Day;
+use crate::{
+    answer::Answer,
+    day::Day,
+    parse::{end_of, ParseError, Source},
+};
 
 const DIRS: [(i32, i32, i32); 6] = [
     (1, 0, 0),
@@ -21,19 +25,24 @@ impl<'a> Day<'a> for Day18 {
     type Input = HashSet<(i32, i32, i32)>;
     type ProcessedInput = HashSet<(i32, i32, i32)>;
 
-    fn parse(input: &'a str) -> Self::Input {
-        input
+    fn parse(input: &'a str) -> Result<Self::Input, ParseError> {
+        let src = Source::new(Self::DAY, input);
+        let cubes = input
             .trim()
             .lines()
             .map(|line| {
-                let (x, yz) = line.split_once(',').unwrap();
-                let (y, z) = yz.split_once(',').unwrap();
-                (x.parse().unwrap(), y.parse().unwrap(), z.parse().unwrap())
+                let (x, yz) = src.split_once(line, ",")?;
+                let (y, z) = src.split_once(yz, ",")?;
+                Ok((src.number(x)?, src.number(y)?, src.number(z)?))
             })
-            .collect()
+            .collect::<Result<Self::Input, _>>()?;
+        match cubes.is_empty() {
+            true => Err(src.error(end_of(input), "a cube")),
+            false => Ok(cubes),
+        }
     }
 
-    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, String) {
+    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, Answer) {
         let ans = input
             .iter()
             .map(|p| {
@@ -42,11 +51,15 @@ impl<'a> Day<'a> for Day18 {
                     .count()
             })
             .sum::<usize>()
-            .to_string();
+            .into();
         (input, ans)
     }
 
-    fn solve_part2(input: Self::ProcessedInput) -> String {
+    fn process(input: Self::Input) -> Self::ProcessedInput {
+        input
+    }
+
+    fn solve_part2(input: Self::ProcessedInput) -> Answer {
         let (x_min, x_max) = input.iter().map(|p| p.0).minmax().into_option().unwrap();
         let (y_min, y_max) = input.iter().map(|p| p.1).minmax().into_option().unwrap();
         let (z_min, z_max) = input.iter().map(|p| p.2).minmax().into_option().unwrap();
@@ -74,7 +87,7 @@ impl<'a> Day<'a> for Day18 {
                 }
             }
         }
-        faces.to_string()
+        faces.into()
     }
 }
 
@@ -101,12 +114,18 @@ mod test_day18 {
 
     #[test]
     fn test_day18_examples() {
-        let input = Day18::parse(EXAMPLE);
+        let input = Day18::parse(EXAMPLE).unwrap();
         let (input, part1) = Day18::solve_part1(input);
         let part2 = Day18::solve_part2(input);
-        assert_eq!(part1, "64");
-        assert_eq!(part2, "58");
+        assert_eq!(part1, Answer::Int(64));
+        assert_eq!(part2, Answer::Int(58));
     }
-}
 
-bench_day!(18);
+    #[test]
+    fn test_day18_parse_errors() {
+        let err = Day18::parse("2,2,2\n1,2\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 4));
+        assert_eq!(err.expected, "\",\"");
+        assert_eq!(Day18::parse("").unwrap_err().expected, "a cube");
+    }
+}
@@ -3,8 +3,13 @@ use std::collections::VecDeque;
 use hashbrown::HashMap;
 use regex::Regex;
 
-use crate::day::Day;
+use crate::{
+    answer::Answer,
+    day::Day,
+    parse::{ParseError, Source},
+};
 
+#[derive(Clone)]
 pub struct Blueprint {
     id: i32,
     ore: i32,
@@ -151,7 +156,7 @@ impl<'a> Day<'a> for Day19 {
     type Input = Vec<Blueprint>;
     type ProcessedInput = Vec<Blueprint>;
 
-    fn parse(input: &'a str) -> Self::Input {
+    fn parse(input: &'a str) -> Result<Self::Input, ParseError> {
         let bp_regex = Regex::new(concat!(
                 r"^Blueprint (?P<id>\d+): ",
                 r"Each ore robot costs (?P<ore>\d+) ore. ",
@@ -159,39 +164,46 @@ impl<'a> Day<'a> for Day19 {
                 r"Each obsidian robot costs (?P<obsidian_ore>\d+) ore and (?P<obsidian_clay>\d+) clay. ",
                 r"Each geode robot costs (?P<geode_ore>\d+) ore and (?P<geode_obsidian>\d+) obsidian.$"
             )).unwrap();
+        let src = Source::new(Self::DAY, input);
         input
             .trim()
             .lines()
             .map(|line| {
-                let captures = bp_regex.captures(line).unwrap();
-                let capture = |name| captures.name(name).unwrap().as_str().parse().unwrap();
-                Blueprint {
-                    id: capture("id"),
-                    ore: capture("ore"),
-                    clay: capture("clay"),
-                    obsidian: (capture("obsidian_ore"), capture("obsidian_clay")),
-                    geode: (capture("geode_ore"), capture("geode_obsidian")),
-                }
+                let captures = bp_regex
+                    .captures(line)
+                    .ok_or_else(|| src.error(line, "a blueprint"))?;
+                let capture = |name| src.number(captures.name(name).unwrap().as_str());
+                Ok(Blueprint {
+                    id: capture("id")?,
+                    ore: capture("ore")?,
+                    clay: capture("clay")?,
+                    obsidian: (capture("obsidian_ore")?, capture("obsidian_clay")?),
+                    geode: (capture("geode_ore")?, capture("geode_obsidian")?),
+                })
             })
             .collect()
     }
 
-    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, String) {
+    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, Answer) {
         let ans = input
             .iter()
             .map(|bp| bp.id * bp.max_geodes(24))
             .sum::<i32>()
-            .to_string();
+            .into();
         (input, ans)
     }
 
-    fn solve_part2(input: Self::ProcessedInput) -> String {
+    fn process(input: Self::Input) -> Self::ProcessedInput {
+        input
+    }
+
+    fn solve_part2(input: Self::ProcessedInput) -> Answer {
         input
             .iter()
             .take(3)
             .map(|bp| bp.max_geodes(32))
             .product::<i32>()
-            .to_string()
+            .into()
     }
 }
 
@@ -207,11 +219,16 @@ mod test_day19 {
 
     #[test]
     fn test_day19_examples() {
-        let input = Day19::parse(EXAMPLE);
+        let input = Day19::parse(EXAMPLE).unwrap();
         let (input, part1) = Day19::solve_part1(input);
-        assert_eq!(part1, "33");
+        assert_eq!(part1, Answer::Int(33));
         assert_eq!(input[1].max_geodes(32), 62);
     }
-}
 
-bench_day!(19);
+    #[test]
+    fn test_day19_parse_errors() {
+        let err = Day19::parse(&EXAMPLE.replace("Each clay", "Each sand")).err().unwrap();
+        assert_eq!((err.line, err.column), (1, 1));
+        assert_eq!(err.expected, "a blueprint");
+    }
+}
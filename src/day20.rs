@@ -1,4 +1,8 @@
-use crate::day::Day;
+use crate::{
+    answer::Answer,
+    day::Day,
+    parse::{end_of, ParseError, Source},
+};
 
 #[derive(Clone)]
 pub struct File {
@@ -41,28 +45,39 @@ impl<'a> Day<'a> for Day20 {
     type Input = File;
     type ProcessedInput = File;
 
-    fn parse(input: &'a str) -> Self::Input {
-        File::new(
-            input
-                .trim()
-                .lines()
-                .map(|line| line.parse().unwrap())
-                .collect(),
-        )
+    fn parse(input: &'a str) -> Result<Self::Input, ParseError> {
+        let src = Source::new(Self::DAY, input);
+        let numbers = input
+            .trim()
+            .lines()
+            .map(|line| src.number(line))
+            .collect::<Result<Vec<_>, _>>()?;
+        // mixing moves numbers modulo one less than their count, and the score is found from 0
+        if numbers.len() < 2 {
+            return Err(src.error(end_of(input), "at least two numbers"));
+        }
+        if !numbers.contains(&0) {
+            return Err(src.error(end_of(input), "a 0 somewhere in the file"));
+        }
+        Ok(File::new(numbers))
     }
 
-    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, String) {
+    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, Answer) {
         let mut file = input.clone();
         file.mix();
-        (input, file.score().to_string())
+        (input, file.score().into())
+    }
+
+    fn process(input: Self::Input) -> Self::ProcessedInput {
+        input
     }
 
-    fn solve_part2(mut file: Self::ProcessedInput) -> String {
+    fn solve_part2(mut file: Self::ProcessedInput) -> Answer {
         file.numbers.iter_mut().for_each(|n| *n *= 811589153);
         for _ in 0..10 {
             file.mix();
         }
-        file.score().to_string()
+        file.score().into()
     }
 }
 
@@ -83,12 +98,19 @@ mod test_day20 {
 
     #[test]
     fn test_day20_examples() {
-        let input = Day20::parse(EXAMPLE);
+        let input = Day20::parse(EXAMPLE).unwrap();
         let (input, part1) = Day20::solve_part1(input);
         let part2 = Day20::solve_part2(input);
-        assert_eq!(part1, "3");
-        assert_eq!(part2, "1623178306");
+        assert_eq!(part1, Answer::Int(3));
+        assert_eq!(part2, Answer::Int(1623178306));
     }
-}
 
-bench_day!(20);
+    #[test]
+    fn test_day20_parse_errors() {
+        let err = Day20::parse("1\n2\n-3\nx\n").err().unwrap();
+        assert_eq!((err.line, err.column), (4, 1));
+        assert_eq!(err.expected, "a number");
+        assert_eq!(Day20::parse("0\n").err().unwrap().expected, "at least two numbers");
+        assert_eq!(Day20::parse("1\n2\n").err().unwrap().expected, "a 0 somewhere in the file");
+    }
+}
@@ -1,10 +1,15 @@
 use hashbrown::HashMap;
 use itertools::Itertools;
 
-use crate::day::Day;
+use crate::{
+    answer::Answer,
+    day::Day,
+    parse::{end_of, ParseError, Source},
+};
 
 pub struct Day21;
 
+#[derive(Clone)]
 pub enum Op {
     Add,
     Sub,
@@ -47,6 +52,7 @@ impl Op {
     }
 }
 
+#[derive(Clone)]
 pub enum Monkey<'a> {
     Num(i64),
     Op(&'a str, &'a str, Op),
@@ -109,16 +115,20 @@ impl<'a> Day<'a> for Day21 {
     type Input = HashMap<&'a str, Monkey<'a>>;
     type ProcessedInput = HashMap<&'a str, Monkey<'a>>;
 
-    fn parse(input: &'a str) -> Self::Input {
-        input
+    fn parse(input: &'a str) -> Result<Self::Input, ParseError> {
+        let src = Source::new(Self::DAY, input);
+        let monkeys = input
             .trim()
             .lines()
             .map(|line| {
-                let (name, job) = line.split_once(": ").unwrap();
+                let (name, job) = src.split_once(line, ": ")?;
                 let monkey = if job.starts_with(|c: char| c.is_ascii_digit()) {
-                    Monkey::Num(job.parse().unwrap())
+                    Monkey::Num(src.number(job)?)
                 } else {
-                    let (lhs, op, rhs) = job.split_ascii_whitespace().collect_tuple().unwrap();
+                    let (lhs, op, rhs) = job
+                        .split_ascii_whitespace()
+                        .collect_tuple()
+                        .ok_or_else(|| src.error(job, "a number or an operation"))?;
                     Monkey::Op(
                         lhs,
                         rhs,
@@ -127,33 +137,48 @@ impl<'a> Day<'a> for Day21 {
                             "-" => Op::Sub,
                             "*" => Op::Mul,
                             "/" => Op::Div,
-                            _ => panic!("unknown operation {op}"),
+                            _ => return Err(src.error(op, "+, -, * or /")),
                         },
                     )
                 };
-                (name, monkey)
+                Ok((name, monkey))
             })
-            .collect()
+            .collect::<Result<HashMap<_, _>, _>>()?;
+        for monkey in monkeys.values() {
+            if let Monkey::Op(lhs, rhs, _) = monkey {
+                if let Some(name) = [lhs, rhs].into_iter().find(|n| !monkeys.contains_key(*n)) {
+                    return Err(src.error(name, "a known monkey"));
+                }
+            }
+        }
+        if let Some(name) = ["root", "humn"].into_iter().find(|n| !monkeys.contains_key(n)) {
+            return Err(src.error(end_of(input), format!("a monkey named {}", name)));
+        }
+        Ok(monkeys)
     }
 
-    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, String) {
+    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, Answer) {
         let ans = input
             .get("root")
             .unwrap()
             .eval_without("", &input)
             .unwrap()
-            .to_string();
+            .into();
         (input, ans)
     }
 
-    fn solve_part2(mut input: Self::ProcessedInput) -> String {
+    fn process(input: Self::Input) -> Self::ProcessedInput {
+        input
+    }
+
+    fn solve_part2(mut input: Self::ProcessedInput) -> Answer {
         let Monkey::Op(_, _, op) = input.get_mut("root").unwrap() else { unreachable!() };
         *op = Op::Eq;
         input
             .get("root")
             .unwrap()
             .enforce_from("humn", 1, &input)
-            .to_string()
+            .into()
     }
 }
 
@@ -182,12 +207,22 @@ mod test_day21 {
 
     #[test]
     fn test_day21_examples() {
-        let input = Day21::parse(EXAMPLE);
+        let input = Day21::parse(EXAMPLE).unwrap();
         let (input, part1) = Day21::solve_part1(input);
         let part2 = Day21::solve_part2(input);
-        assert_eq!(part1, "152");
-        assert_eq!(part2, "301");
+        assert_eq!(part1, Answer::Int(152));
+        assert_eq!(part2, Answer::Int(301));
     }
-}
 
-bench_day!(21);
+    #[test]
+    fn test_day21_parse_errors() {
+        let err = Day21::parse("root: aaaa % bbbb\naaaa: 1\nbbbb: 2\nhumn: 3\n").err().unwrap();
+        assert_eq!((err.line, err.column), (1, 12));
+        assert_eq!(err.expected, "+, -, * or /");
+        let err = Day21::parse("root: aaaa + cccc\naaaa: 1\nhumn: 3\n").err().unwrap();
+        assert_eq!((err.line, err.column), (1, 14));
+        assert_eq!(err.expected, "a known monkey");
+        let err = Day21::parse("root: 5\n").err().unwrap();
+        assert_eq!(err.expected, "a monkey named humn");
+    }
+}
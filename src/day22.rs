@@ -3,7 +3,11 @@ use std::collections::VecDeque;
 use hashbrown::{HashMap, HashSet};
 use itertools::iproduct;
 
-use crate::day::Day;
+use crate::{
+    answer::Answer,
+    day::Day,
+    parse::{end_of, ParseError, Source},
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum Dir {
@@ -21,6 +25,7 @@ enum Step {
     Turn(i32),
 }
 
+#[derive(Clone)]
 pub struct Input {
     start: usize,
     width: usize,
@@ -235,58 +240,70 @@ impl<'a, const N: usize> Day<'a> for Day22Generic<N> {
     type Input = Input;
     type ProcessedInput = Input;
 
-    fn parse(input: &'a str) -> Self::Input {
-        let lines = input.trim_end().lines().collect::<Vec<_>>();
-        let height = lines.len() - 2;
-        let width = lines[..height].iter().map(|s| s.len()).max().unwrap();
+    fn parse(input: &'a str) -> Result<Self::Input, ParseError> {
+        let src = Source::new(Self::DAY, input);
+        let input = input.trim_end();
+        let (map, mut path_string) = input
+            .split_once("\n\n")
+            .ok_or_else(|| src.error(end_of(input), "a blank line before the path"))?;
+        let lines = map.lines().collect::<Vec<_>>();
+        for line in lines.iter() {
+            if let Some(i) = line.find(|c: char| !" .#".contains(c)) {
+                return Err(src.error(&line[i..], "an open tile or a wall"));
+            }
+        }
+        let height = lines.len();
+        let width = lines.iter().map(|s| s.len()).max().unwrap_or(0);
         let (width, height) = (width / N, height / N);
         let mut faces = HashMap::new();
         for y in 0..height {
             for x in 0..width {
-                if lines[y * N].chars().nth(x * N).map_or(true, |c| c == ' ') {
+                if lines[y * N].chars().nth(x * N).is_none_or(|c| c == ' ') {
                     continue;
                 }
-                let face = lines
+                let rows = &lines[y * N..(y + 1) * N];
+                if let Some(row) = rows.iter().find(|row| row.len() < (x + 1) * N) {
+                    return Err(src.error(end_of(row), format!("a face {} tiles wide", N)));
+                }
+                let face = rows
                     .iter()
-                    .skip(y * N)
-                    .take(N)
-                    .flat_map(|line| line.chars().skip(x * N).take(N).map(|c| c == '#'))
+                    .flat_map(|row| row[x * N..(x + 1) * N].chars().map(|c| c == '#'))
                     .collect();
                 faces.insert((x, y), face);
             }
         }
-        let start = (0..width).find(|&x| faces.contains_key(&(x, 0))).unwrap();
+        let start = (0..width)
+            .find(|&x| faces.contains_key(&(x, 0)))
+            .ok_or_else(|| src.error(map, "a face on the first row"))?;
         let mut path = Vec::new();
-        let mut path_string = *lines.last().unwrap();
         while !path_string.is_empty() {
-            match &path_string[..1] {
-                "L" => {
-                    path.push(Step::Turn(-1));
-                    path_string = &path_string[1..];
-                }
-                "R" => {
-                    path.push(Step::Turn(1));
-                    path_string = &path_string[1..];
-                }
-                _ => {
-                    let end = path_string
-                        .find(|c: char| !c.is_ascii_digit())
-                        .unwrap_or(path_string.len());
-                    path.push(Step::Move(path_string[..end].parse().unwrap()));
-                    path_string = &path_string[end..];
-                }
+            if let Some(rest) = path_string.strip_prefix('L') {
+                path.push(Step::Turn(-1));
+                path_string = rest;
+            } else if let Some(rest) = path_string.strip_prefix('R') {
+                path.push(Step::Turn(1));
+                path_string = rest;
+            } else {
+                let end = path_string
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(path_string.len());
+                let steps = path_string[..end]
+                    .parse()
+                    .map_err(|_| src.error(path_string, "a number of steps, L or R"))?;
+                path.push(Step::Move(steps));
+                path_string = &path_string[end..];
             }
         }
-        Input {
+        Ok(Input {
             start,
             width,
             height,
             faces,
             path,
-        }
+        })
     }
 
-    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, String) {
+    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, Answer) {
         let (w, h) = (input.width as i32, input.height as i32);
         let wrap = |pos: (i32, i32)| (pos.0.rem_euclid(w) as usize, pos.1.rem_euclid(h) as usize);
         let connections = iproduct!(input.faces.keys(), DIRS)
@@ -298,14 +315,18 @@ impl<'a, const N: usize> Day<'a> for Day22Generic<N> {
                 ((pos, dir), (conn, false, dir.opposite()))
             })
             .collect();
-        let ans = score(&input, &connections, N).to_string();
+        let ans = score(&input, &connections, N).into();
         (input, ans)
     }
 
-    fn solve_part2(input: Self::ProcessedInput) -> String {
+    fn process(input: Self::Input) -> Self::ProcessedInput {
+        input
+    }
+
+    fn solve_part2(input: Self::ProcessedInput) -> Answer {
         let orientations = fold_net(&input.faces);
         let connections = cube_connections(&orientations);
-        score(&input, &connections, N).to_string()
+        score(&input, &connections, N).into()
     }
 }
 
@@ -333,12 +354,18 @@ mod test_day22 {
 
     #[test]
     fn test_day22_examples() {
-        let input = Day22Generic::<4>::parse(EXAMPLE);
+        let input = Day22Generic::<4>::parse(EXAMPLE).unwrap();
         let (input, part1) = Day22Generic::<4>::solve_part1(input);
         let part2 = Day22Generic::<4>::solve_part2(input);
-        assert_eq!(part1, "6032");
-        assert_eq!(part2, "5031");
+        assert_eq!(part1, Answer::Int(6032));
+        assert_eq!(part2, Answer::Int(5031));
     }
-}
 
-bench_day!(22);
+    #[test]
+    fn test_day22_parse_errors() {
+        let err = Day22Generic::<4>::parse(&EXAMPLE.replace("10R5", "10X5")).err().unwrap();
+        assert_eq!(err.expected, "a number of steps, L or R");
+        let err = Day22Generic::<4>::parse(EXAMPLE.split("\n\n").next().unwrap()).err().unwrap();
+        assert_eq!(err.expected, "a blank line before the path");
+    }
+}
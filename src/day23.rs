@@ -1,6 +1,10 @@
 use hashbrown::{HashMap, HashSet};
 
-use crate::day::Day;
+use crate::{
+    answer::Answer,
+    day::Day,
+    parse::{end_of, ParseError, Source},
+};
 
 const DIRS: [(i32, i32); 8] = [
     (-1, -1),
@@ -55,19 +59,25 @@ impl<'a> Day<'a> for Day23 {
     type Input = HashSet<(i32, i32)>;
     type ProcessedInput = HashSet<(i32, i32)>;
 
-    fn parse(input: &'a str) -> Self::Input {
-        input
-            .trim_end()
-            .lines()
-            .enumerate()
-            .flat_map(|(y, line)| {
-                line.char_indices()
-                    .filter_map(move |(x, c)| (c == '#').then_some((x as i32, y as i32)))
-            })
-            .collect()
+    fn parse(input: &'a str) -> Result<Self::Input, ParseError> {
+        let src = Source::new(Self::DAY, input);
+        let mut elves = HashSet::new();
+        for (y, line) in input.trim_end().lines().enumerate() {
+            for (x, c) in line.char_indices() {
+                match c {
+                    '#' => drop(elves.insert((x as i32, y as i32))),
+                    '.' => {}
+                    _ => return Err(src.error(&line[x..], "an elf or empty ground")),
+                }
+            }
+        }
+        match elves.is_empty() {
+            true => Err(src.error(end_of(input), "an elf")),
+            false => Ok(elves),
+        }
     }
 
-    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, String) {
+    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, Answer) {
         let mut elves = input.clone();
         let mut sides = SIDES;
         for _ in 0..10 {
@@ -82,15 +92,19 @@ impl<'a> Day<'a> for Day23 {
             max.1 = i32::max(max.1, e.1);
         });
         let ans = (1 + max.0 - min.0) * (1 + max.1 - min.1) - elves.len() as i32;
-        (input, ans.to_string())
+        (input, ans.into())
     }
 
-    fn solve_part2(mut elves: Self::ProcessedInput) -> String {
+    fn process(input: Self::Input) -> Self::ProcessedInput {
+        input
+    }
+
+    fn solve_part2(mut elves: Self::ProcessedInput) -> Answer {
         let mut sides = SIDES;
         (1..)
             .find(|_| !round(&mut elves, &mut sides))
             .unwrap()
-            .to_string()
+            .into()
     }
 }
 
@@ -111,12 +125,18 @@ mod test_day23 {
 
     #[test]
     fn test_day23_examples() {
-        let input = Day23::parse(EXAMPLE);
+        let input = Day23::parse(EXAMPLE).unwrap();
         let (input, part1) = Day23::solve_part1(input);
         let part2 = Day23::solve_part2(input);
-        assert_eq!(part1, "110");
-        assert_eq!(part2, "20");
+        assert_eq!(part1, Answer::Int(110));
+        assert_eq!(part2, Answer::Int(20));
     }
-}
 
-bench_day!(23);
+    #[test]
+    fn test_day23_parse_errors() {
+        let err = Day23::parse("..#\n.x.\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.expected, "an elf or empty ground");
+        assert_eq!(Day23::parse("...\n").unwrap_err().expected, "an elf");
+    }
+}
@@ -2,8 +2,13 @@ use std::{iter, mem};
 
 use hashbrown::{HashMap, HashSet};
 
-use crate::day::Day;
+use crate::{
+    answer::Answer,
+    day::Day,
+    parse::{end_of, ParseError, Source},
+};
 
+#[derive(Clone)]
 pub struct Valley {
     width: i32,
     height: i32,
@@ -53,18 +58,42 @@ impl<'a> Day<'a> for Day24 {
     type Input = Valley;
     type ProcessedInput = (i32, Valley);
 
-    fn parse(input: &'a str) -> Self::Input {
+    fn parse(input: &'a str) -> Result<Self::Input, ParseError> {
+        let src = Source::new(Self::DAY, input);
         let lines = input.trim_end().lines().collect::<Vec<_>>();
+        if lines.len() < 3 || lines[0].len() < 3 {
+            return Err(src.error(input, "a valley surrounded by walls"));
+        }
         let (width, height) = (lines[0].len() - 2, lines.len() - 2);
+        // without an entrance at the top left and an exit at the bottom right there's no way across
+        let (entrance, exit) = (
+            format!("#.{}", "#".repeat(width)),
+            format!("{}.#", "#".repeat(width)),
+        );
+        if lines[0] != entrance {
+            return Err(src.error(lines[0], format!("a wall like {:?}", entrance)));
+        }
+        if lines[height + 1] != exit {
+            return Err(src.error(lines[height + 1], format!("a wall like {:?}", exit)));
+        }
         let mut blizzards = HashMap::new();
         for y in 0..height {
+            let row = lines[y + 1];
+            if row.len() != width + 2 {
+                let rest = row.get(width + 2..).unwrap_or(end_of(row));
+                return Err(src.error(rest, format!("a row of {} tiles", width + 2)));
+            }
+            if !row.starts_with('#') || !row.ends_with('#') {
+                return Err(src.error(row, "a row between walls"));
+            }
             for x in 0..width {
-                let dir = match lines[y + 1].as_bytes()[x + 1] {
+                let dir = match row.as_bytes()[x + 1] {
                     b'>' => (1, 0),
                     b'<' => (-1, 0),
                     b'v' => (0, 1),
                     b'^' => (0, -1),
-                    _ => continue,
+                    b'.' => continue,
+                    _ => return Err(src.error(&row[x + 1..], "a blizzard or open ground")),
                 };
                 blizzards
                     .entry((x as i32, y as i32))
@@ -72,22 +101,22 @@ impl<'a> Day<'a> for Day24 {
                     .push(dir);
             }
         }
-        Valley {
+        Ok(Valley {
             width: width as i32,
             height: height as i32,
             blizzards,
-        }
+        })
     }
 
-    fn solve_part1(mut valley: Self::Input) -> (Self::ProcessedInput, String) {
+    fn solve_part1(mut valley: Self::Input) -> (Self::ProcessedInput, Answer) {
         let time = valley.navigate((0, -1), (valley.width - 1, valley.height));
-        ((time, valley), time.to_string())
+        ((time, valley), time.into())
     }
 
-    fn solve_part2((mut time, mut valley): Self::ProcessedInput) -> String {
+    fn solve_part2((mut time, mut valley): Self::ProcessedInput) -> Answer {
         time += valley.navigate((valley.width - 1, valley.height), (0, -1));
         time += valley.navigate((0, -1), (valley.width - 1, valley.height));
-        time.to_string()
+        time.into()
     }
 }
 
@@ -107,12 +136,21 @@ mod test_day24 {
 
     #[test]
     fn test_day24_examples() {
-        let input = Day24::parse(EXAMPLE);
+        let input = Day24::parse(EXAMPLE).unwrap();
         let (input, part1) = Day24::solve_part1(input);
         let part2 = Day24::solve_part2(input);
-        assert_eq!(part1, "18");
-        assert_eq!(part2, "54");
+        assert_eq!(part1, Answer::Int(18));
+        assert_eq!(part2, Answer::Int(54));
     }
-}
 
-bench_day!(24);
+    #[test]
+    fn test_day24_parse_errors() {
+        let err = Day24::parse(&EXAMPLE.replace("#>>.<^<#", "#>>.<^<.")).err().unwrap();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.expected, "a row between walls");
+        let truncated = EXAMPLE.lines().take(4).collect::<Vec<_>>().join("\n");
+        let err = Day24::parse(&truncated).err().unwrap();
+        assert_eq!((err.line, err.column), (4, 1));
+        assert_eq!(err.expected, "a wall like \"######.#\"");
+    }
+}
@@ -1,6 +1,10 @@
 use std::iter;
 
-use crate::day::Day;
+use crate::{
+    answer::Answer,
+    day::Day,
+    parse::{ParseError, Source},
+};
 
 fn from_snafu(string: &str) -> i64 {
     string
@@ -49,16 +53,26 @@ impl<'a> Day<'a> for Day25 {
     type Input = Vec<i64>;
     type ProcessedInput = ();
 
-    fn parse(input: &'a str) -> Self::Input {
-        input.trim_end().lines().map(from_snafu).collect()
+    fn parse(input: &'a str) -> Result<Self::Input, ParseError> {
+        let src = Source::new(Self::DAY, input);
+        input
+            .trim_end()
+            .lines()
+            .map(|line| match line.find(|c: char| !"=-012".contains(c)) {
+                Some(i) => Err(src.error(&line[i..], "a SNAFU digit")),
+                None => Ok(from_snafu(line)),
+            })
+            .collect()
     }
 
-    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, String) {
-        ((), to_snafu(input.into_iter().sum()))
+    fn solve_part1(input: Self::Input) -> (Self::ProcessedInput, Answer) {
+        ((), to_snafu(input.into_iter().sum()).into())
     }
 
-    fn solve_part2(_: Self::ProcessedInput) -> String {
-        "Merry Christmas!".to_string()
+    fn process(_: Self::Input) -> Self::ProcessedInput {}
+
+    fn solve_part2(_: Self::ProcessedInput) -> Answer {
+        "Merry Christmas!".into()
     }
 }
 
@@ -98,10 +112,15 @@ mod test_day25 {
         assert_eq!(to_snafu(12345), "1-0---0");
         assert_eq!(to_snafu(314159265), "1121-1110-1=0");
 
-        let input = Day25::parse(EXAMPLE);
+        let input = Day25::parse(EXAMPLE).unwrap();
         let (_, part1) = Day25::solve_part1(input);
-        assert_eq!(part1, "2=-1=0");
+        assert_eq!(part1, Answer::from("2=-1=0"));
     }
-}
 
-bench_day!(25);
+    #[test]
+    fn test_day25_parse_errors() {
+        let err = Day25::parse("1=-0-2\n12111\n2=0=3\n").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 5));
+        assert_eq!(err.expected, "a SNAFU digit");
+    }
+}
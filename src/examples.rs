@@ -0,0 +1,172 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::{fs, io, path::PathBuf};
+
+use crate::{
+    answer::Answer,
+    answers::Answers,
+    config::Config,
+    day::{Error, Part},
+    web::Fetcher,
+};
+
+lazy_static! {
+    static ref ARTICLE: Regex =
+        Regex::new(r#"(?s)<article class="day-desc">(.*?)</article>"#).unwrap();
+    static ref EXAMPLE: Regex = Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap();
+    static ref EMPHASIS: Regex =
+        Regex::new(r"(?s)<code><em>(.*?)</em></code>|<em><code>(.*?)</code></em>|<em>(.*?)</em>")
+            .unwrap();
+    static ref TAG: Regex = Regex::new(r"<[^>]*>").unwrap();
+}
+
+fn unescape(html: &str) -> String {
+    TAG.replace_all(html, "")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+pub fn page_path(config: &Config, day: usize) -> PathBuf {
+    config.cache_file(format!("day{}.html", day))
+}
+
+pub fn input_path(config: &Config, day: usize, example: usize) -> PathBuf {
+    config.cache_file(format!("day{}.example{}.in", day, example))
+}
+
+pub fn answers_path(config: &Config, day: usize) -> PathBuf {
+    config.cache_file(format!("day{}.example.ans", day))
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Examples {
+    pub inputs: Vec<String>,
+    pub answers: Answers,
+}
+
+impl Examples {
+    // The first example in a part is the one worked through, so the first emphasised text after
+    // it is taken to be its answer; emphasis inside later examples and the emphasised question
+    // each part ends with are skipped. A part without an example of its own reuses part 1's, and
+    // is searched from the start.
+    pub fn extract(page: &str) -> Self {
+        let mut examples = Self::default();
+        for (article, part) in ARTICLE.captures_iter(page).zip([Part::One, Part::Two]) {
+            let article = &article[1];
+            examples
+                .inputs
+                .extend(EXAMPLE.captures_iter(article).map(|c| unescape(&c[1])));
+            let after_example = EXAMPLE.find(article).map_or(0, |example| example.end());
+            let prose = EXAMPLE.replace_all(&article[after_example..], "");
+            let answer = EMPHASIS
+                .captures_iter(&prose)
+                .filter_map(|c| c.iter().skip(1).flatten().next())
+                .map(|m| unescape(m.as_str()).trim().to_string())
+                .find(|answer| !answer.is_empty() && !answer.ends_with('?'));
+            if let Some(answer) = answer {
+                examples.answers.set(part, Answer::parse(&answer));
+            }
+        }
+        examples
+    }
+
+    pub fn load(config: &Config, day: usize) -> Self {
+        Self {
+            inputs: (1..)
+                .map_while(|example| fs::read_to_string(input_path(config, day, example)).ok())
+                .collect(),
+            answers: Answers::load_from(&answers_path(config, day)),
+        }
+    }
+
+    pub fn save(&self, config: &Config, day: usize) -> io::Result<()> {
+        for (i, input) in self.inputs.iter().enumerate() {
+            fs::write(input_path(config, day, i + 1), input)?;
+        }
+        self.answers.save_to(&answers_path(config, day))
+    }
+}
+
+// The cached page is only used once it includes part 2, since that unlocks after solving part 1.
+pub fn load_page(config: &Config, fetcher: &mut Fetcher, day: usize) -> Result<String, Error> {
+    match fs::read_to_string(page_path(config, day)) {
+        Ok(page) if ARTICLE.find_iter(&page).count() == 2 => Ok(page),
+        _ => {
+            let page = fetcher.download_page(day)?;
+            drop(fs::write(page_path(config, day), &page));
+            Ok(page)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_examples {
+    use super::*;
+    use indoc::indoc;
+
+    const PAGE: &str = indoc! {r#"
+        <main>
+        <article class="day-desc"><h2>--- Day 6: Tuning Trouble ---</h2>
+        <p>For example, suppose you receive the following datastream buffer:</p>
+        <pre><code>mjqjpq<em>m</em>gbljsphdztnvjfqwrcgsmlb
+        </code></pre>
+        <p>The first marker is after character <code><em>7</em></code>.</p>
+        <p>Here are a few more examples:</p>
+        <ul>
+        <li><code>bvwbjplbgvbhsrlpgdmjqwftvncz</code>: first marker after character <code><em>5</em></code></li>
+        </ul>
+        <p>How many characters need to be processed before the first start-of-packet marker is detected?</p>
+        </article>
+        <article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+        <pre><code>a &lt;- b &amp;&amp; c
+        </code></pre>
+        <p>That answer is <em><code>19</code></em>.</p>
+        </article>
+        </main>
+    "#};
+
+    #[test]
+    fn test_extract_examples() {
+        let examples = Examples::extract(PAGE);
+        assert_eq!(
+            examples.inputs,
+            ["mjqjpqmgbljsphdztnvjfqwrcgsmlb\n", "a <- b && c\n"]
+        );
+        assert_eq!(examples.answers.get(Part::One), Some(&Answer::Int(7)));
+        assert_eq!(examples.answers.get(Part::Two), Some(&Answer::Int(19)));
+
+        let part1_only = PAGE
+            .split("<article")
+            .take(2)
+            .collect::<Vec<_>>()
+            .join("<article");
+        let examples = Examples::extract(&part1_only);
+        assert_eq!(examples.inputs.len(), 1);
+        assert_eq!(examples.answers.get(Part::Two), None);
+    }
+
+    #[test]
+    fn test_extract_plain_answer() {
+        let page = indoc! {r#"
+            <article class="day-desc"><h2>--- Day 1: Calorie Counting ---</h2>
+            <pre><code>1000
+            <em>2000</em>
+            </code></pre>
+            <p>In the example above, this is <em>24000</em> (carried by the fourth Elf).</p>
+            <pre><code>1
+            <em>2</em>
+            </code></pre>
+            <p>Find the Elf carrying the most Calories. <em>How many total Calories is that Elf
+            carrying?</em></p>
+            </article>
+        "#};
+        let examples = Examples::extract(page);
+        assert_eq!(examples.answers.get(Part::One), Some(&Answer::Int(24000)));
+
+        let examples = Examples::extract(&page.replace("<em>24000</em>", "24000"));
+        assert_eq!(examples.answers.get(Part::One), None);
+    }
+}
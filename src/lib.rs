@@ -0,0 +1,35 @@
+pub mod answer;
+pub mod answers;
+pub mod baseline;
+pub mod bench;
+pub mod config;
+pub mod day;
+pub mod examples;
+pub mod memory;
+pub mod parse;
+pub mod registry;
+pub mod report;
+pub mod runner;
+pub mod stats;
+pub mod submissions;
+pub mod util;
+pub mod web;
+
+pub use day::Day;
+use registry::{DaySolution, Solution};
+
+macro_rules! declare_days {
+    ($($day:literal),+) => {
+        paste::paste! {
+            $(pub mod [<day $day>];)+
+
+            pub static DAYS: &[&dyn Solution] = &[
+                $(&DaySolution::<[<day $day>]::[<Day $day>]>::new()),+
+            ];
+        }
+    };
+}
+
+declare_days!(
+    01, 02, 03, 04, 05, 06, 07, 08, 09, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25
+);
@@ -1,100 +1,525 @@
-#![feature(test)]
-
-mod day;
-mod util;
-
-extern crate test;
-
-use day::Day;
-use std::env;
-
-#[macro_export]
-macro_rules! bench_day {
-    ($day:literal) => {
-        paste::paste! {
-            #[cfg(not(debug_assertions))]
-            #[cfg(test)]
-            mod [<bench_day $day>] {
-                use $crate::day::Day;
-                use super::*;
-                use test::Bencher;
-
-                #[bench]
-                fn [<bench_day $day _overall>] (b: &mut Bencher) {
-                    let input = [<Day $day>]::get_input().unwrap();
-                    b.iter(|| {
-                        let input = [<Day $day>]::parse(&input);
-                        let (input, part1) = [<Day $day>]::solve_part1(input);
-                        let part2 = [<Day $day>]::solve_part2(input);
-                        (part1, part2)
-                    })
+use aoc2022::{
+    answers,
+    baseline::BaselineRun,
+    bench::Harness,
+    config::{self, Config},
+    day::Part,
+    examples::{self, Examples},
+    registry::{self, Solution},
+    report::{self, DayReport},
+    runner,
+    submissions::{self, Outcome, Verdict},
+    web::Fetcher,
+    DAYS,
+};
+use std::{
+    collections::BTreeMap,
+    env,
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+    process,
+    time::{Duration, Instant},
+};
+
+struct Args {
+    selector: Option<String>,
+    part: Option<Part>,
+    input: Option<String>,
+    repeat: usize,
+    jobs: usize,
+    // overrides the configured timeout, where a timeout of None means there isn't one
+    timeout: Option<Option<Duration>>,
+    json: bool,
+    record: bool,
+}
+
+impl Args {
+    fn parse<I: Iterator<Item = String>>(mut args: I) -> Result<Self, String> {
+        let mut parsed = Self {
+            selector: None,
+            part: None,
+            input: None,
+            repeat: 1,
+            jobs: 1,
+            timeout: None,
+            json: false,
+            record: false,
+        };
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--part" => {
+                    parsed.part = match args.next().as_deref() {
+                        Some("1") => Some(Part::One),
+                        Some("2") => Some(Part::Two),
+                        _ => return Err("Expected 1 or 2 after --part".to_string()),
+                    }
+                }
+                "--input" => match args.next() {
+                    Some(path) => parsed.input = Some(path),
+                    None => return Err("Expected a path or - after --input".to_string()),
+                },
+                "--repeat" => {
+                    parsed.repeat = match args.next().and_then(|n| n.parse().ok()) {
+                        Some(n) if n > 0 => n,
+                        _ => return Err("Expected a number of runs after --repeat".to_string()),
+                    }
+                }
+                "--jobs" => {
+                    parsed.jobs = match args.next().and_then(|n| n.parse().ok()) {
+                        Some(n) if n > 0 => n,
+                        _ => return Err("Expected a number of threads after --jobs".to_string()),
+                    }
                 }
+                "--timeout" => {
+                    parsed.timeout = match args.next().as_deref().and_then(config::parse_timeout) {
+                        Some(timeout) => Some(timeout),
+                        None => {
+                            return Err("Expected a number of seconds after --timeout".to_string())
+                        }
+                    }
+                }
+                "--json" => parsed.json = true,
+                "--record" => parsed.record = true,
+                option if option.starts_with("--") => {
+                    return Err(format!("Unknown option {}", option))
+                }
+                _ if parsed.selector.is_none() => parsed.selector = Some(arg),
+                _ => return Err(format!("Unexpected argument {}", arg)),
             }
         }
+        if parsed.record && parsed.input.is_some() {
+            return Err("Can't record answers for an input given with --input".to_string());
+        }
+        Ok(parsed)
+    }
+}
+
+fn read_input(path: &str) -> io::Result<String> {
+    if path == "-" {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        Ok(input)
+    } else {
+        fs::read_to_string(path)
+    }
+}
+
+fn solve(
+    solution: &dyn Solution,
+    config: &Config,
+    part: Option<Part>,
+    input_path: Option<&str>,
+    repeat: usize,
+) -> DayReport {
+    let input = match input_path {
+        Some(path) => {
+            read_input(path).map_err(|err| format!("Couldn't read input from {} [{}]", path, err))
+        }
+        None => solution.get_input(config).map_err(|err| err.to_string()),
     };
+    match input {
+        Ok(input) => solution.solve_repeated(&input, part, repeat),
+        Err(err) => DayReport::failed(solution.day(), err),
+    }
 }
 
-macro_rules! import_days {
-    ($day:literal) => {
-        paste::paste! {
-            mod [<day $day>];
-            use [<day $day>]::[<Day $day>];
+fn run(config: &Config, registered: &[usize], args: Args) -> Result<(), String> {
+    let days = match args.selector {
+        None => registered.last().copied().into_iter().collect(),
+        Some(selector) => registry::select_days(&selector, registered)?,
+    };
+    if args.input.is_some() && days.len() != 1 {
+        return Err("Expected a single day to run with --input".to_string());
+    }
+    let timeout = args.timeout.unwrap_or(config.timeout);
+    // inputs are downloaded before solving so that days solved in parallel keep to the delay
+    let download_errors = match args.input {
+        None => runner::download_missing(config, &days, |_, _| {}).failed,
+        Some(_) => BTreeMap::new(),
+    };
+    let solve_day = |day| match (registry::lookup(DAYS, day), download_errors.get(&day)) {
+        (Ok(_), Some(err)) => DayReport::failed(day, err.clone()),
+        (Ok(solution), None) => {
+            let (config, part, input, repeat) =
+                (config.clone(), args.part, args.input.clone(), args.repeat);
+            runner::isolated(day, timeout, move || {
+                solve(solution, &config, part, input.as_deref(), repeat)
+            })
         }
+        (Err(err), _) => DayReport::failed(day, err),
     };
-    ($day:literal, $($days:literal),+) => {
-        import_days!($day);
-        import_days!($($days),+);
+    let start_time = Instant::now();
+    let mut reports = Vec::new();
+    runner::solve_in_order(&days, args.jobs, solve_day, |mut report| {
+        let day = report.day;
+        // stored answers are only for our own input
+        if args.input.is_none() {
+            answers::check(config, &mut report);
+        }
+        if args.record {
+            if let Err(err) = answers::record(config, &report) {
+                eprintln!("Couldn't record answers for day {} [{}]", day, err);
+            }
+        }
+        if !args.json {
+            report.print();
+        }
+        reports.push(report);
+    });
+    if args.json {
+        report::print_json(&reports);
+    } else if reports.len() > 1 {
+        report::print_summary(&reports);
+    }
+    if args.jobs > 1 && !args.json {
+        println!();
+        println!(
+            "wall time {}ms, per-day solve times summing to {}ms over {} jobs",
+            1000.0 * start_time.elapsed().as_secs_f32(),
+            1000.0
+                * reports
+                    .iter()
+                    .map(DayReport::total_elapsed)
+                    .sum::<Duration>()
+                    .as_secs_f32(),
+            args.jobs
+        );
     }
+    let failed = reports.iter().any(|report| report.error.is_some());
+    if failed || (!args.record && reports.iter().any(DayReport::mismatched)) {
+        process::exit(1);
+    }
+    Ok(())
 }
 
-macro_rules! solve {
-    ($day:literal) => {{
-        paste::paste! {
-            match [<Day $day>]::get_input() {
-                Ok(input) => [<Day $day>]::solve_and_print(&input),
-                Err(err) => eprintln!("{}", err),
+// Selects every registered day unless a selector is given.
+fn parse_days<I: Iterator<Item = String>>(
+    registered: &[usize],
+    mut args: I,
+) -> Result<Vec<usize>, String> {
+    match (args.next(), args.next()) {
+        (None, _) => Ok(registered.to_vec()),
+        (Some(option), _) if option.starts_with("--") => Err(format!("Unknown option {}", option)),
+        (Some(selector), None) => registry::select_days(&selector, registered),
+        (Some(_), Some(arg)) => Err(format!("Unexpected argument {}", arg)),
+    }
+}
+
+fn fetch<I: Iterator<Item = String>>(
+    config: &Config,
+    registered: &[usize],
+    args: I,
+) -> Result<(), String> {
+    let days = parse_days(registered, args)?;
+    let downloads = runner::download_missing(config, &days, |day, result| match result {
+        Ok(()) => println!("day{:02}: fetched", day),
+        Err(err) => eprintln!("day{:02}: {}", day, err),
+    });
+    let describe = |days: &[usize]| match days {
+        [] => "none".to_string(),
+        days => registry::describe_days(days),
+    };
+    let failed = downloads.failed.keys().copied().collect::<Vec<_>>();
+    println!();
+    println!("fetched: {}", describe(&downloads.fetched));
+    println!("skipped: {} (already cached)", describe(&downloads.skipped));
+    println!("failed: {}", describe(&failed));
+    if !failed.is_empty() {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+fn extract_examples<I: Iterator<Item = String>>(
+    config: &Config,
+    registered: &[usize],
+    args: I,
+) -> Result<(), String> {
+    let mut args = args.peekable();
+    if args.next_if(|arg| arg == "--check").is_some() {
+        return check_examples(config, registered, args);
+    }
+    let mut fetcher = Fetcher::new(config);
+    let mut failed = false;
+    for day in parse_days(registered, args)? {
+        let examples = match examples::load_page(config, &mut fetcher, day) {
+            Ok(page) => Examples::extract(&page),
+            Err(err) => {
+                eprintln!("day{:02}: {}", day, err);
+                failed = true;
+                continue;
             }
+        };
+        if let Err(err) = examples.save(config, day) {
+            eprintln!("day{:02}: Couldn't save examples [{}]", day, err);
+            failed = true;
+            continue;
         }
-    }};
-    ($day:literal, $($days:literal),+) => {{
-        solve!($day);
-        solve!($($days),+)
-    }}
+        let answer = |part| {
+            examples
+                .answers
+                .get(part)
+                .map_or("none".to_string(), |answer| answer.to_string())
+        };
+        println!(
+            "day{:02}: {} example input(s), part1: {}, part2: {}",
+            day,
+            examples.inputs.len(),
+            answer(Part::One),
+            answer(Part::Two)
+        );
+    }
+    if failed {
+        process::exit(1);
+    }
+    Ok(())
 }
 
-macro_rules! match_days {
-    ($day_string:ident, $($days:literal),+) => {{
-        match $day_string {
-            "all" => solve!($($days),+),
-            day => match day.parse::<usize>() {
-                Err(err) => eprintln!("Expected day number (or \"all\") as argument ({})", err),
-                $(Ok($days) => solve!($days)),+,
-                Ok(n) if (1..=25).contains(&n) => todo!(),
-                Ok(_) => eprintln!("That's not a day of advent!"),
+// The saved answers are usually given for the first example input, so that's the one solved.
+fn check_examples<I: Iterator<Item = String>>(
+    config: &Config,
+    registered: &[usize],
+    args: I,
+) -> Result<(), String> {
+    let mut failed = false;
+    for day in parse_days(registered, args)? {
+        let examples = Examples::load(config, day);
+        let (Some(solution), Some(input)) =
+            (registry::find(DAYS, day), examples.inputs.first().cloned())
+        else {
+            eprintln!("day{:02}: No saved examples to check", day);
+            failed = true;
+            continue;
+        };
+        let mut report =
+            runner::isolated(day, config.timeout, move || solution.solve(&input, None));
+        for part in report.parts.iter_mut() {
+            part.expected = examples.answers.get(part.part).cloned();
+        }
+        report.print();
+        failed |= report.error.is_some() || report.mismatched();
+    }
+    if failed {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+fn submit<I: Iterator<Item = String>>(config: &Config, args: I) -> Result<(), String> {
+    let usage = || "Expected a day and part to submit like \"submit 3 1\"".to_string();
+    let mut force = false;
+    let mut positional = Vec::new();
+    for arg in args {
+        match arg.as_str() {
+            "--force" => force = true,
+            option if option.starts_with("--") => return Err(format!("Unknown option {}", option)),
+            _ => positional.push(arg),
+        }
+    }
+    let (day, part) = match positional.as_slice() {
+        [day, part] => (day, part),
+        _ => return Err(usage()),
+    };
+    let day = day.parse::<usize>().map_err(|_| usage())?;
+    let part = match part.as_str() {
+        "1" => Part::One,
+        "2" => Part::Two,
+        _ => return Err(usage()),
+    };
+    let solution = registry::lookup(DAYS, day)?;
+    match submissions::submit(config, solution, part, force)? {
+        Outcome::AlreadyDone(done) if done.verdict == Verdict::Correct => println!(
+            "Part {} of day {} was already accepted with the answer {}",
+            part.number(),
+            day,
+            done.answer
+        ),
+        Outcome::AlreadyDone(done) => println!(
+            "Part {} of day {} was already solved when {} was submitted",
+            part.number(),
+            day,
+            done.answer
+        ),
+        Outcome::Submitted {
+            submission,
+            warning,
+            unrecorded,
+        } => {
+            if let Some(warning) = warning {
+                eprintln!("Submitted {} anyway: {}", submission.answer, warning);
+            }
+            println!(
+                "day{:02}: submitted {} for part {}",
+                day,
+                submission.answer,
+                part.number()
+            );
+            println!("{}", submission.verdict);
+            for err in unrecorded {
+                eprintln!("{}", err);
             }
         }
-    }}
+    }
+    Ok(())
 }
 
-macro_rules! declare_main {
-    ($last_day:literal => $($days:literal),+) => {
-        import_days!($($days),+);
-        fn main() {
-            #[allow(clippy::zero_prefixed_literal)]
-            match env::args().nth(1).as_deref() {
-                None => solve!($last_day),
-                Some(day) => match_days!(day, $($days),+),
+fn bench<I: Iterator<Item = String>>(
+    config: &Config,
+    registered: &[usize],
+    mut args: I,
+) -> Result<(), String> {
+    let mut harness = Harness::default();
+    let (mut compare, mut save) = (false, false);
+    let mut threshold = config.regression_threshold;
+    let mut rest = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--samples" => {
+                harness.samples = match args.next().and_then(|n| n.parse().ok()) {
+                    Some(n) if n > 0 => n,
+                    _ => return Err("Expected a number of samples after --samples".to_string()),
+                }
             }
+            "--warmup" => {
+                harness.warmup = match args.next().and_then(|secs| secs.parse().ok()) {
+                    Some(secs) if secs >= 0.0 && f64::is_finite(secs) => {
+                        Duration::from_secs_f64(secs)
+                    }
+                    _ => return Err("Expected a number of seconds after --warmup".to_string()),
+                }
+            }
+            "--baseline" => compare = true,
+            "--save-baseline" => save = true,
+            "--threshold" => {
+                threshold = match args
+                    .next()
+                    .and_then(|n| n.trim_end_matches('%').parse().ok())
+                {
+                    Some(n) => n,
+                    None => return Err("Expected a percentage after --threshold".to_string()),
+                }
+            }
+            _ => rest.push(arg),
         }
+    }
+    let days = parse_days(registered, rest.into_iter())?;
+    let mut run = BaselineRun::new(config, compare, save, threshold)?;
+    let mut failed = false;
+    for day in days {
+        let result = registry::lookup(DAYS, day)
+            .and_then(|solution| runner::bench_day(config, solution, &harness));
+        match result {
+            Ok(bench) => {
+                bench.print(run.compared.as_ref(), threshold);
+                run.add(&bench);
+            }
+            Err(err) => {
+                eprintln!("day{:02}: {}", day, err);
+                failed = true;
+            }
+        }
+    }
+    if let Some(path) = run.save(config)? {
+        println!();
+        println!("saved baseline to {}", path.display());
+    }
+    if compare {
+        println!();
+        match run.regressed.as_slice() {
+            [] => println!("no regressions over {}%", threshold),
+            regressed => println!("regressed over {}%: {}", threshold, regressed.join(", ")),
+        }
+    }
+    if failed || !run.regressed.is_empty() {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+// Options that apply to every command are taken out before the command's own arguments are parsed.
+fn take_global_options<I: Iterator<Item = String>>(
+    config: &mut Config,
+    mut args: I,
+) -> Result<Vec<String>, String> {
+    let mut rest = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--cache-dir" => match args.next() {
+                Some(dir) => config.cache_dir = PathBuf::from(dir),
+                None => return Err("Expected a directory after --cache-dir".to_string()),
+            },
+            _ => rest.push(arg),
+        }
+    }
+    Ok(rest)
+}
+
+fn exit_with_error<E: Display>(err: E) -> ! {
+    eprintln!("{}", err);
+    process::exit(1)
+}
+
+fn main() {
+    let mut config = Config::load().unwrap_or_else(|err| exit_with_error(err));
+    let args = take_global_options(&mut config, env::args().skip(1))
+        .unwrap_or_else(|err| exit_with_error(err));
+    if let Err(err) = fs::create_dir_all(&config.cache_dir) {
+        exit_with_error(format!(
+            "Couldn't create cache directory {} [{}]",
+            config.cache_dir.display(),
+            err
+        ));
+    }
+    let registered = DAYS.iter().map(|s| s.day()).collect::<Vec<_>>();
+    let mut args = args.into_iter().peekable();
+    let result = match args.peek().map(String::as_str) {
+        Some("fetch") => fetch(&config, &registered, args.skip(1)),
+        Some("examples") => extract_examples(&config, &registered, args.skip(1)),
+        Some("submit") => submit(&config, args.skip(1)),
+        Some("bench") => bench(&config, &registered, args.skip(1)),
+        _ => Args::parse(args).and_then(|args| run(&config, &registered, args)),
     };
-    ($head_day:literal, $($last_days:literal),+ => $($days:literal),+) => {
-        declare_main!($($last_days),+ => $($days),+);
-    };
-    ($($days:literal),+) => {
-        declare_main!($($days),+ => $($days),+);
+    if let Err(err) = result {
+        exit_with_error(err);
     }
 }
 
-declare_main!(
-    01, 02, 03, 04, 05, 06, 07, 08, 09, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25
-);
+#[cfg(test)]
+mod test_main {
+    use super::*;
+
+    #[test]
+    fn test_parse_args() {
+        let parse = |args: &[&str]| Args::parse(args.iter().map(|arg| arg.to_string()));
+        let args = parse(&["5", "--input", "-", "--part", "2", "--repeat", "10"]).unwrap();
+        assert_eq!(args.jobs, 1);
+        assert_eq!(args.selector.as_deref(), Some("5"));
+        assert_eq!(args.input.as_deref(), Some("-"));
+        assert_eq!(args.part, Some(Part::Two));
+        assert_eq!(args.repeat, 10);
+        assert!(parse(&["5", "--repeat", "0"]).is_err());
+        assert_eq!(parse(&["all", "--jobs", "4"]).unwrap().jobs, 4);
+        assert!(parse(&["all", "--jobs", "0"]).is_err());
+        assert_eq!(
+            parse(&["all", "--timeout", "2.5"]).unwrap().timeout,
+            Some(Some(Duration::from_millis(2500)))
+        );
+        assert_eq!(
+            parse(&["all", "--timeout", "0"]).unwrap().timeout,
+            Some(None)
+        );
+        assert!(parse(&["5", "--input"]).is_err());
+        assert!(parse(&["5", "--input", "x.in", "--record"]).is_err());
+    }
+
+    #[test]
+    fn test_global_options() {
+        let mut config = Config::default();
+        let args = ["fetch", "--cache-dir", "/tmp/aoc", "1-5"].map(String::from);
+        let rest = take_global_options(&mut config, args.into_iter()).unwrap();
+        assert_eq!(rest, ["fetch", "1-5"]);
+        assert_eq!(config.cache_dir, PathBuf::from("/tmp/aoc"));
+    }
+}
@@ -0,0 +1,181 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fmt::{self, Display, Formatter},
+};
+
+pub const COUNTING: bool = cfg!(feature = "count-alloc");
+
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Allocations {
+    pub count: u64,
+    pub bytes: u64,
+    pub peak: u64,
+}
+
+impl Allocations {
+    pub fn to_json(&self) -> String {
+        format!(
+            r#"{{"count":{},"bytes":{},"peak_bytes":{}}}"#,
+            self.count, self.bytes, self.peak
+        )
+    }
+}
+
+impl Display for Allocations {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} allocated, peak {}",
+            self.count,
+            format_bytes(self.bytes),
+            format_bytes(self.peak)
+        )
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    let mut size = bytes as f64;
+    for unit in ["B", "KiB", "MiB"] {
+        if size < 1024.0 {
+            return match unit {
+                "B" => format!("{}B", bytes),
+                _ => format!("{:.1}{}", size, unit),
+            };
+        }
+        size /= 1024.0;
+    }
+    format!("{:.1}GiB", size)
+}
+
+#[derive(Clone, Copy)]
+struct Counts {
+    count: u64,
+    bytes: u64,
+    live: i64,
+    peak: i64,
+}
+
+const NO_COUNTS: Counts = Counts {
+    count: 0,
+    bytes: 0,
+    live: 0,
+    peak: 0,
+};
+
+// Counts are kept per thread so that days solved in parallel don't see each other's allocations.
+thread_local! {
+    static COUNTS: Cell<Counts> = const { Cell::new(NO_COUNTS) };
+}
+
+fn update(f: impl FnOnce(&mut Counts)) {
+    // the thread local is gone while the thread is being torn down
+    let _ = COUNTS.try_with(|counts| {
+        let mut current = counts.get();
+        f(&mut current);
+        counts.set(current);
+    });
+}
+
+fn counts() -> Counts {
+    COUNTS.try_with(Cell::get).unwrap_or(NO_COUNTS)
+}
+
+fn record_alloc(size: usize) {
+    update(|counts| {
+        counts.count += 1;
+        counts.bytes += size as u64;
+        counts.live += size as i64;
+        counts.peak = counts.peak.max(counts.live);
+    })
+}
+
+fn record_dealloc(size: usize) {
+    update(|counts| counts.live -= size as i64)
+}
+
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+// Counts what `f` allocates on this thread, with the peak measured from the bytes already live
+// when it starts; without the count-alloc feature nothing is counted.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Allocations>) {
+    if !COUNTING {
+        return (f(), None);
+    }
+    let start = counts();
+    update(|counts| counts.peak = counts.live);
+    let result = f();
+    let end = counts();
+    let allocations = Allocations {
+        count: end.count - start.count,
+        bytes: end.bytes - start.bytes,
+        peak: (end.peak - start.live).max(0) as u64,
+    };
+    update(|counts| counts.peak = counts.peak.max(start.peak));
+    (result, Some(allocations))
+}
+
+#[cfg(test)]
+mod test_memory {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(1000), "1000B");
+        assert_eq!(format_bytes(1536), "1.5KiB");
+        assert_eq!(format_bytes(3 << 20), "3.0MiB");
+        assert_eq!(format_bytes(5 << 30), "5.0GiB");
+    }
+
+    #[test]
+    fn test_measure() {
+        let (sum, allocations) = measure(|| {
+            let v = vec![1u64; 1000];
+            drop(vec![0u8; 100]);
+            v.iter().sum::<u64>()
+        });
+        assert_eq!(sum, 1000);
+        assert_eq!(allocations.is_some(), COUNTING);
+        if let Some(allocations) = allocations {
+            assert_eq!(allocations.count, 2);
+            assert_eq!(allocations.bytes, 8100);
+            assert_eq!(allocations.peak, 8100);
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use std::{
+    error,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: usize,
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+    pub source_line: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Couldn't parse input for day {} at line {}, column {}: expected {}, found {}",
+            self.day, self.line, self.column, self.expected, self.found
+        )?;
+        writeln!(f, "    {}", self.source_line)?;
+        write!(f, "    {:>1$}", "^", self.column)
+    }
+}
+
+impl error::Error for ParseError {}
+
+pub fn end_of(string: &str) -> &str {
+    &string[string.len()..]
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Source<'a> {
+    day: usize,
+    input: &'a str,
+}
+
+impl<'a> Source<'a> {
+    pub fn new(day: usize, input: &'a str) -> Self {
+        Self { day, input }
+    }
+
+    // Points the error at `at`, which should be a slice of the input; anything else is reported
+    // at the start of the input.
+    pub fn error<S: Into<String>>(&self, at: &str, expected: S) -> ParseError {
+        let offset = (at.as_ptr() as usize)
+            .checked_sub(self.input.as_ptr() as usize)
+            .filter(|&offset| offset + at.len() <= self.input.len())
+            .unwrap_or(0);
+        let (before, after) = self.input.split_at(offset);
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let source_line = self.input[line_start..].lines().next().unwrap_or("");
+        let rest = if at.is_empty() { after } else { at };
+        let found = match rest.lines().next() {
+            Some(line) if !line.is_empty() && line.chars().count() > 20 => {
+                format!("{:?}...", line.chars().take(20).collect::<String>())
+            }
+            Some(line) if !line.is_empty() => format!("{:?}", line),
+            _ if after.trim_end().is_empty() => "end of input".to_string(),
+            _ => "end of line".to_string(),
+        };
+        ParseError {
+            day: self.day,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            expected: expected.into(),
+            found,
+            source_line: source_line.to_string(),
+        }
+    }
+
+    pub fn number<T: FromStr>(&self, string: &'a str) -> Result<T, ParseError> {
+        string.parse().map_err(|_| self.error(string, "a number"))
+    }
+
+    pub fn split_once(
+        &self,
+        string: &'a str,
+        delimiter: &str,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        string
+            .split_once(delimiter)
+            .ok_or_else(|| self.error(end_of(string), format!("{:?}", delimiter)))
+    }
+
+    pub fn strip_prefix(&self, string: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+        string
+            .strip_prefix(prefix)
+            .ok_or_else(|| self.error(string, format!("{:?}", prefix)))
+    }
+}
+
+#[cfg(test)]
+mod test_parse {
+    use super::*;
+
+    #[test]
+    fn test_error_locations() {
+        let input = "addx 3\nnoop\naddx x5\n";
+        let src = Source::new(10, input);
+        let line = input.lines().nth(2).unwrap();
+
+        let err = src.number::<i32>(&line[5..]).unwrap_err();
+        assert_eq!((err.day, err.line, err.column), (10, 3, 6));
+        assert_eq!(err.expected, "a number");
+        assert_eq!(err.found, "\"x5\"");
+        assert_eq!(err.source_line, "addx x5");
+        assert_eq!(
+            err.to_string(),
+            "Couldn't parse input for day 10 at line 3, column 6: expected a number, found \"x5\"\n    addx x5\n         ^"
+        );
+
+        let err = src.split_once(line, ",").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 8));
+        assert_eq!(err.found, "end of input");
+
+        let err = src.strip_prefix(&input[7..], "addx ").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.found, "\"noop\"");
+    }
+}
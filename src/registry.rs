@@ -0,0 +1,312 @@
+use std::{collections::BTreeSet, marker::PhantomData, time::Instant};
+
+use crate::{
+    answer::Answer,
+    bench::{DayBench, Harness},
+    config::Config,
+    day::{Day, Error, Part},
+    memory,
+    parse::ParseError,
+    report::{DayReport, PartReport},
+    stats::Stats,
+};
+
+pub trait Parsed<'a> {
+    fn solve_part1(self: Box<Self>) -> (Box<dyn Processed<'a> + 'a>, Answer);
+    fn process(self: Box<Self>) -> Box<dyn Processed<'a> + 'a>;
+}
+
+pub trait Processed<'a> {
+    fn solve_part2(self: Box<Self>) -> Answer;
+}
+
+pub trait Solution: Sync {
+    fn day(&self) -> usize;
+    fn get_input(&self, config: &Config) -> Result<String, Error>;
+    fn parse<'a>(&self, input: &'a str) -> Result<Box<dyn Parsed<'a> + 'a>, ParseError>;
+    fn bench(&self, input: &str, harness: &Harness) -> Result<DayBench, ParseError>;
+
+    fn solve(&self, input: &str, part: Option<Part>) -> DayReport {
+        let start_time = Instant::now();
+        let (input, parse_allocations) = memory::measure(|| self.parse(input));
+        let parsed_time = Instant::now();
+        let mut report = DayReport {
+            day: self.day(),
+            parse_elapsed: Some(parsed_time - start_time),
+            parse_stats: None,
+            parse_allocations,
+            parts: Vec::new(),
+            error: None,
+        };
+        let input = match input {
+            Ok(input) => input,
+            Err(err) => {
+                report.error = Some(err.to_string());
+                return report;
+            }
+        };
+
+        // processing the input for part 2 alone counts towards part 2, so it's measured with it
+        let (solve_part2, part1_time): (Box<dyn FnOnce() -> Answer>, _) = match part {
+            Some(Part::Two) => (Box::new(|| input.process().solve_part2()), parsed_time),
+            _ => {
+                let ((processed_input, part1_answer), allocations) =
+                    memory::measure(|| input.solve_part1());
+                let part1_time = Instant::now();
+                report.parts.push(PartReport {
+                    part: Part::One,
+                    answer: part1_answer,
+                    elapsed: part1_time - parsed_time,
+                    stats: None,
+                    allocations,
+                    expected: None,
+                });
+                if part == Some(Part::One) {
+                    return report;
+                }
+                (Box::new(|| processed_input.solve_part2()), part1_time)
+            }
+        };
+
+        let (part2_answer, allocations) = memory::measure(solve_part2);
+        let part2_time = Instant::now();
+        report.parts.push(PartReport {
+            part: Part::Two,
+            answer: part2_answer,
+            elapsed: part2_time - part1_time,
+            stats: None,
+            allocations,
+            expected: None,
+        });
+        report
+    }
+
+    // Solves the input `runs` times, reporting the answers from the first run along with timing
+    // statistics for each phase over all of them.
+    fn solve_repeated(&self, input: &str, part: Option<Part>, runs: usize) -> DayReport {
+        let mut report = self.solve(input, part);
+        if report.error.is_some() || runs <= 1 {
+            return report;
+        }
+        let mut parse_times = vec![report.parse_elapsed.unwrap_or_default()];
+        let mut part_times = report
+            .parts
+            .iter()
+            .map(|p| vec![p.elapsed])
+            .collect::<Vec<_>>();
+        for _ in 1..runs {
+            let rerun = self.solve(input, part);
+            parse_times.extend(rerun.parse_elapsed);
+            for (times, part) in part_times.iter_mut().zip(rerun.parts) {
+                times.push(part.elapsed);
+            }
+        }
+        report.parse_stats = Stats::new(&parse_times);
+        for (part, times) in report.parts.iter_mut().zip(part_times) {
+            part.stats = Stats::new(&times);
+        }
+        report
+    }
+}
+
+pub struct DaySolution<D>(PhantomData<D>);
+
+impl<D> DaySolution<D> {
+    pub const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<D> Default for DaySolution<D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct ParsedInput<'a, D: Day<'a>>(D::Input);
+
+struct ProcessedInput<'a, D: Day<'a>>(D::ProcessedInput);
+
+impl<'a, D: Day<'a> + 'a> Parsed<'a> for ParsedInput<'a, D> {
+    fn solve_part1(self: Box<Self>) -> (Box<dyn Processed<'a> + 'a>, Answer) {
+        let (processed_input, answer) = D::solve_part1(self.0);
+        (Box::new(ProcessedInput::<D>(processed_input)), answer)
+    }
+
+    fn process(self: Box<Self>) -> Box<dyn Processed<'a> + 'a> {
+        Box::new(ProcessedInput::<D>(D::process(self.0)))
+    }
+}
+
+impl<'a, D: Day<'a> + 'a> Processed<'a> for ProcessedInput<'a, D> {
+    fn solve_part2(self: Box<Self>) -> Answer {
+        D::solve_part2(self.0)
+    }
+}
+
+impl<D: for<'a> Day<'a> + Sync + 'static> Solution for DaySolution<D> {
+    fn day(&self) -> usize {
+        <D as Day>::DAY
+    }
+
+    fn get_input(&self, config: &Config) -> Result<String, Error> {
+        <D as Day>::get_input(config)
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Box<dyn Parsed<'a> + 'a>, ParseError> {
+        Ok(Box::new(ParsedInput::<D>(D::parse(input)?)))
+    }
+
+    // Each phase after parsing consumes its input, so it's given a fresh clone for every run.
+    fn bench(&self, input: &str, harness: &Harness) -> Result<DayBench, ParseError> {
+        let parsed = D::parse(input)?;
+        let processed = D::process(parsed.clone());
+        Ok(DayBench {
+            day: self.day(),
+            parse: harness.measure(|| input, |input| D::parse(input)),
+            part1: harness.measure(|| parsed.clone(), |input| D::solve_part1(input)),
+            part2: harness.measure(|| processed.clone(), |input| D::solve_part2(input)),
+        })
+    }
+}
+
+pub fn find(days: &[&'static dyn Solution], day: usize) -> Option<&'static dyn Solution> {
+    days.iter().copied().find(|solution| solution.day() == day)
+}
+
+// Selects days from a list of days and ranges like "3-7,12,20-", where "all" and the ranges only
+// include registered days.
+pub fn select_days(selector: &str, registered: &[usize]) -> Result<Vec<usize>, String> {
+    let parse_day = |day: &str| match day.parse::<usize>() {
+        Err(err) => Err(format!(
+            "Expected day selector like \"3-7,12,20-\" (or \"all\") as argument ({})",
+            err
+        )),
+        Ok(n) if (1..=25).contains(&n) => Ok(n),
+        Ok(_) => Err("That's not a day of advent!".to_string()),
+    };
+    let mut days = BTreeSet::new();
+    for item in selector.split(',').map(str::trim) {
+        if item == "all" {
+            days.extend(registered);
+        } else if let Some((start, end)) = item.split_once('-') {
+            let start = if start.is_empty() {
+                1
+            } else {
+                parse_day(start)?
+            };
+            let end = if end.is_empty() { 25 } else { parse_day(end)? };
+            if start > end {
+                return Err(format!(
+                    "The range {} is backwards, try {}-{}",
+                    item, end, start
+                ));
+            }
+            days.extend(registered.iter().filter(|day| (start..=end).contains(day)));
+        } else {
+            days.insert(parse_day(item)?);
+        }
+    }
+    Ok(days.into_iter().collect())
+}
+
+// Finds a day's solution, or says which days are registered if it isn't one of them.
+pub fn lookup(days: &[&'static dyn Solution], day: usize) -> Result<&'static dyn Solution, String> {
+    find(days, day).ok_or_else(|| {
+        let registered = days
+            .iter()
+            .map(|solution| solution.day())
+            .collect::<Vec<_>>();
+        format!(
+            "day {} not implemented (registered days: {})",
+            day,
+            describe_days(&registered)
+        )
+    })
+}
+
+// Describes a sorted list of days compactly, like "1-3,5,8-10".
+pub fn describe_days(days: &[usize]) -> String {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for &day in days {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == day => *end = day,
+            _ => ranges.push((day, day)),
+        }
+    }
+    ranges
+        .into_iter()
+        .map(|(start, end)| match end - start {
+            0 => start.to_string(),
+            _ => format!("{}-{}", start, end),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod test_registry {
+    use super::*;
+    use crate::day02::Day02;
+    use std::time::Duration;
+
+    const EXAMPLE: &str = "A Y\nB X\nC Z\n";
+
+    #[test]
+    fn test_day_solution() {
+        let solution: &dyn Solution = &DaySolution::<Day02>::new();
+        assert_eq!(solution.day(), 2);
+
+        let (processed, part1) = solution.parse(EXAMPLE).unwrap().solve_part1();
+        assert_eq!(part1, Answer::Int(15));
+        assert_eq!(processed.solve_part2(), Answer::Int(12));
+
+        let report = solution.solve(EXAMPLE, Some(Part::Two));
+        assert_eq!(report.parts.len(), 1);
+        assert_eq!(report.parts[0].part, Part::Two);
+        assert_eq!(report.parts[0].answer, Answer::Int(12));
+
+        let report = solution.solve_repeated(EXAMPLE, None, 5);
+        assert_eq!(report.parse_stats.unwrap().runs, 5);
+        assert!(report.parts.iter().all(|p| p.stats.unwrap().runs == 5));
+
+        let harness = Harness {
+            warmup: Duration::ZERO,
+            samples: 10,
+        };
+        let bench = solution.bench(EXAMPLE, &harness).unwrap();
+        assert_eq!(bench.day, 2);
+        assert_eq!(bench.part2.stats.runs + bench.part2.outliers, 10);
+        assert!(solution.bench("A Q\n", &harness).is_err());
+    }
+
+    #[test]
+    fn test_select_days() {
+        let registered = [1, 2, 3, 5, 8, 13, 21];
+        assert_eq!(select_days("all", &registered).unwrap(), registered);
+        assert_eq!(select_days("3", &registered).unwrap(), [3]);
+        assert_eq!(select_days("2-8", &registered).unwrap(), [2, 3, 5, 8]);
+        assert_eq!(select_days("13-,-2", &registered).unwrap(), [1, 2, 13, 21]);
+        assert_eq!(select_days("5,3-5,1", &registered).unwrap(), [1, 3, 5]);
+        assert!(select_days("26", &registered).is_err());
+        assert!(select_days("3-x", &registered).is_err());
+        assert!(select_days("7-3", &registered).is_err());
+    }
+
+    #[test]
+    fn test_describe_days() {
+        assert_eq!(describe_days(&[1, 2, 3, 5, 8, 9, 10, 13]), "1-3,5,8-10,13");
+        assert_eq!(describe_days(&[]), "");
+    }
+
+    #[test]
+    fn test_lookup() {
+        static DAY02: DaySolution<Day02> = DaySolution::new();
+        let days: &[&dyn Solution] = &[&DAY02];
+        assert_eq!(lookup(days, 2).unwrap().day(), 2);
+        assert_eq!(
+            lookup(days, 3).err().unwrap(),
+            "day 3 not implemented (registered days: 2)"
+        );
+    }
+}
@@ -0,0 +1,242 @@
+use std::{fmt::Display, time::Duration};
+
+use crate::{answer::Answer, day::Part, memory::Allocations, stats::Stats};
+
+pub struct PartReport {
+    pub part: Part,
+    pub answer: Answer,
+    pub elapsed: Duration,
+    pub stats: Option<Stats>,
+    pub allocations: Option<Allocations>,
+    pub expected: Option<Answer>,
+}
+
+impl PartReport {
+    pub fn passed(&self) -> Option<bool> {
+        self.expected.as_ref().map(|e| *e == self.answer)
+    }
+}
+
+pub struct DayReport {
+    pub day: usize,
+    pub parse_elapsed: Option<Duration>,
+    pub parse_stats: Option<Stats>,
+    pub parse_allocations: Option<Allocations>,
+    pub parts: Vec<PartReport>,
+    pub error: Option<String>,
+}
+
+impl DayReport {
+    pub fn failed<E: Display>(day: usize, error: E) -> Self {
+        Self {
+            day,
+            parse_elapsed: None,
+            parse_stats: None,
+            parse_allocations: None,
+            parts: Vec::new(),
+            error: Some(error.to_string()),
+        }
+    }
+
+    pub fn mismatched(&self) -> bool {
+        self.parts.iter().any(|p| p.passed() == Some(false))
+    }
+
+    pub fn status(&self) -> &'static str {
+        if self.error.is_some() {
+            "FAILED"
+        } else if self.mismatched() {
+            "WRONG"
+        } else if !self.parts.is_empty() && self.parts.iter().all(|p| p.passed() == Some(true)) {
+            "pass"
+        } else {
+            "done"
+        }
+    }
+
+    pub fn total_elapsed(&self) -> Duration {
+        self.parse_elapsed.unwrap_or_default() + self.parts.iter().map(|p| p.elapsed).sum()
+    }
+
+    pub fn print(&self) {
+        if let Some(parse_elapsed) = self.parse_elapsed {
+            println!();
+            println!("day{:02}:", self.day);
+            println!(
+                "  parsing: ... ({})",
+                details(parse_elapsed, &self.parse_stats, &self.parse_allocations)
+            );
+            for part in self.parts.iter() {
+                let check = match (part.passed(), &part.expected) {
+                    (Some(true), _) => " [pass]".to_string(),
+                    (Some(false), Some(expected)) => format!(" [FAIL: expected {}]", expected),
+                    _ => String::new(),
+                };
+                println!(
+                    "  part{}: {} ({}){}",
+                    part.part.number(),
+                    part.answer,
+                    details(part.elapsed, &part.stats, &part.allocations),
+                    check
+                );
+            }
+        }
+        // without the header above, the error has to say which day it's for
+        match (&self.error, self.parse_elapsed) {
+            (Some(err), Some(_)) => eprintln!("{}", err),
+            (Some(err), None) => eprintln!("day{:02}: {}", self.day, err),
+            (None, _) => {}
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        let parts = self
+            .parts
+            .iter()
+            .map(|part| {
+                format!(
+                    r#"{{"part":{},"answer":{},"elapsed_ns":{},"stats":{},"allocations":{},"expected":{},"passed":{}}}"#,
+                    part.part.number(),
+                    json_answer(&part.answer),
+                    part.elapsed.as_nanos(),
+                    part.stats.map_or("null".to_string(), |s| s.to_json()),
+                    part.allocations.map_or("null".to_string(), |a| a.to_json()),
+                    part.expected
+                        .as_ref()
+                        .map_or("null".to_string(), json_answer),
+                    part.passed().map_or("null".to_string(), |p| p.to_string())
+                )
+            })
+            .collect::<Vec<_>>();
+        format!(
+            r#"{{"day":{},"parse_ns":{},"parse_stats":{},"parse_allocations":{},"parts":[{}],"error":{}}}"#,
+            self.day,
+            self.parse_elapsed
+                .map_or("null".to_string(), |d| d.as_nanos().to_string()),
+            self.parse_stats.map_or("null".to_string(), |s| s.to_json()),
+            self.parse_allocations
+                .map_or("null".to_string(), |a| a.to_json()),
+            parts.join(","),
+            self.error
+                .as_deref()
+                .map_or("null".to_string(), json_string)
+        )
+    }
+}
+
+pub fn print_json(reports: &[DayReport]) {
+    let days = reports.iter().map(DayReport::to_json).collect::<Vec<_>>();
+    println!(
+        r#"{{"days":[{}],"total_ns":{},"failed":{}}}"#,
+        days.join(","),
+        reports
+            .iter()
+            .map(DayReport::total_elapsed)
+            .sum::<Duration>()
+            .as_nanos(),
+        reports.iter().filter(|r| r.error.is_some()).count()
+    );
+}
+
+pub fn print_summary(reports: &[DayReport]) {
+    println!();
+    println!("summary:");
+    for report in reports {
+        let detail = match &report.error {
+            Some(err) => err.lines().next().unwrap_or_default().to_string(),
+            None => format!("{}ms", 1000.0 * report.total_elapsed().as_secs_f32()),
+        };
+        println!("  day{:02}  {:<6}  {}", report.day, report.status(), detail);
+    }
+    let failed = reports.iter().filter(|r| r.error.is_some()).count();
+    println!(
+        "{} of {} days solved, {} failed",
+        reports.len() - failed,
+        reports.len(),
+        failed
+    );
+}
+
+fn details(elapsed: Duration, stats: &Option<Stats>, allocations: &Option<Allocations>) -> String {
+    let timing = match stats {
+        Some(stats) => stats.to_string(),
+        None => format!("elapsed {}ms", 1000.0 * elapsed.as_secs_f32()),
+    };
+    match allocations {
+        Some(allocations) => format!("{}; {}", timing, allocations),
+        None => timing,
+    }
+}
+
+fn json_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Text(text) => json_string(text),
+        _ => answer.to_string(),
+    }
+}
+
+fn json_string(string: &str) -> String {
+    let mut json = String::with_capacity(string.len() + 2);
+    json.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+#[cfg(test)]
+mod test_report {
+    use super::*;
+
+    #[test]
+    fn test_day_report_json() {
+        let report = DayReport {
+            day: 5,
+            parse_elapsed: Some(Duration::from_nanos(1500)),
+            parse_stats: Stats::new(&[1500, 1000, 2000].map(Duration::from_nanos)),
+            parse_allocations: None,
+            parts: vec![
+                PartReport {
+                    part: Part::One,
+                    answer: Answer::from("C\"M\\Z\n"),
+                    elapsed: Duration::from_nanos(20),
+                    stats: None,
+                    allocations: None,
+                    expected: None,
+                },
+                PartReport {
+                    part: Part::Two,
+                    answer: Answer::Int(12),
+                    elapsed: Duration::from_nanos(30),
+                    stats: None,
+                    allocations: Some(Allocations {
+                        count: 3,
+                        bytes: 120,
+                        peak: 80,
+                    }),
+                    expected: Some(Answer::Int(12)),
+                },
+            ],
+            error: None,
+        };
+        assert_eq!(
+            report.to_json(),
+            r#"{"day":5,"parse_ns":1500,"parse_stats":{"runs":3,"min_ns":1000,"median_ns":1500,"mean_ns":1500,"stddev_ns":408},"parse_allocations":null,"parts":[{"part":1,"answer":"C\"M\\Z\n","elapsed_ns":20,"stats":null,"allocations":null,"expected":null,"passed":null},{"part":2,"answer":12,"elapsed_ns":30,"stats":null,"allocations":{"count":3,"bytes":120,"peak_bytes":80},"expected":12,"passed":true}],"error":null}"#
+        );
+        assert_eq!(
+            DayReport::failed(3, "no input").to_json(),
+            r#"{"day":3,"parse_ns":null,"parse_stats":null,"parse_allocations":null,"parts":[],"error":"no input"}"#
+        );
+        assert_eq!(report.status(), "done");
+        assert_eq!(DayReport::failed(3, "no input").status(), "FAILED");
+    }
+}
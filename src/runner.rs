@@ -0,0 +1,264 @@
+use std::{
+    any::Any,
+    cell::Cell,
+    collections::BTreeMap,
+    env, fs,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, RecvTimeoutError},
+        Once,
+    },
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    bench::{DayBench, Harness},
+    config::Config,
+    day,
+    registry::Solution,
+    report::DayReport,
+    web::Fetcher,
+};
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown panic".to_string(),
+        },
+    }
+}
+
+fn is_day_thread(name: Option<&str>) -> bool {
+    name.and_then(|name| name.strip_prefix("day"))
+        .is_some_and(|day| day.len() == 2 && day.bytes().all(|b| b.is_ascii_digit()))
+}
+
+thread_local! {
+    static PANIC_LOCATION: Cell<Option<String>> = const { Cell::new(None) };
+}
+
+// A panicking day is reported along with its results, so for day threads the hook only records
+// where the panic happened; the default hook still prints it when a backtrace is asked for.
+fn quiet_day_panics() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let default = panic::take_hook();
+        let backtrace = env::var_os("RUST_BACKTRACE").is_some_and(|value| value != "0");
+        panic::set_hook(Box::new(move |info| {
+            if !is_day_thread(thread::current().name()) || backtrace {
+                default(info);
+            }
+            PANIC_LOCATION.set(info.location().map(ToString::to_string));
+        }));
+    });
+}
+
+fn catch_panic(day: usize, solve: impl FnOnce() -> DayReport) -> DayReport {
+    panic::catch_unwind(AssertUnwindSafe(solve)).unwrap_or_else(|payload| {
+        let location = PANIC_LOCATION
+            .take()
+            .map_or(String::new(), |location| format!(" at {}", location));
+        let message = panic_message(payload);
+        DayReport::failed(
+            day,
+            format!("day {} panicked{}: {}", day, location, message),
+        )
+    })
+}
+
+// Solves a day on its own thread so that a panic or running past the timeout fails just that day;
+// a day that times out is left running in the background until the program exits.
+pub fn isolated<S>(day: usize, timeout: Option<Duration>, solve: S) -> DayReport
+where
+    S: FnOnce() -> DayReport + Send + 'static,
+{
+    quiet_day_panics();
+    let (sender, receiver) = mpsc::channel();
+    let spawned = thread::Builder::new()
+        .name(format!("day{:02}", day))
+        .spawn(move || drop(sender.send(catch_panic(day, solve))));
+    let handle = match spawned {
+        Ok(handle) => handle,
+        Err(err) => return DayReport::failed(day, format!("Couldn't start day {} [{}]", day, err)),
+    };
+    let report = match timeout {
+        Some(timeout) => receiver.recv_timeout(timeout),
+        None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+    };
+    match report {
+        Ok(report) => report,
+        Err(RecvTimeoutError::Timeout) => DayReport::failed(
+            day,
+            format!(
+                "day {} timed out after {}s",
+                day,
+                timeout.unwrap_or_default().as_secs_f32()
+            ),
+        ),
+        Err(RecvTimeoutError::Disconnected) => {
+            let message = handle
+                .join()
+                .err()
+                .map_or("no report".to_string(), panic_message);
+            DayReport::failed(day, format!("day {} panicked: {}", day, message))
+        }
+    }
+}
+
+pub fn bench_day(
+    config: &Config,
+    solution: &dyn Solution,
+    harness: &Harness,
+) -> Result<DayBench, String> {
+    let input = solution.get_input(config).map_err(|err| err.to_string())?;
+    solution
+        .bench(&input, harness)
+        .map_err(|err| err.to_string())
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Downloads {
+    pub fetched: Vec<usize>,
+    pub skipped: Vec<usize>,
+    pub failed: BTreeMap<usize, String>,
+}
+
+// Downloads the inputs that aren't cached yet one after another with a single fetcher, so that
+// solving days in parallel still keeps to the request delay; `downloaded` hears about each day
+// as it's fetched or fails.
+pub fn download_missing<F>(config: &Config, days: &[usize], mut downloaded: F) -> Downloads
+where
+    F: FnMut(usize, &Result<(), String>),
+{
+    let mut fetcher = Fetcher::new(config);
+    let mut downloads = Downloads::default();
+    for &day in days {
+        let path = day::input_path(config, day);
+        if path.exists() {
+            downloads.skipped.push(day);
+            continue;
+        }
+        let result = fetcher
+            .download_input(day)
+            .map_err(|err| err.to_string())
+            .and_then(|input| {
+                fs::write(&path, input)
+                    .map_err(|err| format!("Couldn't write {} [{}]", path.display(), err))
+            });
+        downloaded(day, &result);
+        match result {
+            Ok(()) => downloads.fetched.push(day),
+            Err(err) => drop(downloads.failed.insert(day, err)),
+        }
+    }
+    downloads
+}
+
+// Solves the days on `jobs` worker threads, handing on each report in day order as soon as it and
+// every report before it are done.
+pub fn solve_in_order<S, F>(days: &[usize], jobs: usize, solve: S, mut finished: F)
+where
+    S: Fn(usize) -> DayReport + Sync,
+    F: FnMut(DayReport),
+{
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let (sender, next, solve) = (sender.clone(), &next, &solve);
+            scope.spawn(move || {
+                while let Some(&day) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    if sender.send((day, solve(day))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+        let mut done = BTreeMap::new();
+        let mut pending = days.iter().peekable();
+        for (day, report) in receiver {
+            done.insert(day, report);
+            while let Some(report) = pending.peek().and_then(|day| done.remove(*day)) {
+                pending.next();
+                finished(report);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod test_runner {
+    use super::*;
+    use std::{env, process};
+
+    #[test]
+    fn test_isolated() {
+        let report = isolated(3, None, || DayReport::failed(3, "no input"));
+        assert_eq!(report.error.as_deref(), Some("no input"));
+        let line = line!() + 1;
+        let report = isolated(3, None, || panic!("oh no"));
+        assert_eq!(
+            report.error.unwrap(),
+            format!("day 3 panicked at src/runner.rs:{}:43: oh no", line)
+        );
+        let report = isolated(3, Some(Duration::from_millis(10)), || {
+            thread::sleep(Duration::from_secs(1));
+            DayReport::failed(3, "")
+        });
+        assert_eq!(report.error.as_deref(), Some("day 3 timed out after 0.01s"));
+    }
+
+    #[test]
+    fn test_is_day_thread() {
+        assert!(is_day_thread(Some("day03")));
+        assert!(!is_day_thread(Some(
+            "day03::test_day03::test_day03_examples"
+        )));
+        assert!(!is_day_thread(Some("main")));
+        assert!(!is_day_thread(None));
+    }
+
+    #[test]
+    fn test_download_missing() {
+        let cache_dir = env::temp_dir().join(format!("aoc2022-runner-{}", process::id()));
+        fs::create_dir_all(&cache_dir).unwrap();
+        fs::write(cache_dir.join("day1.in"), "1000\n").unwrap();
+        fs::write(cache_dir.join(".session_cookie"), "53616c7465645f5f\n").unwrap();
+        let config = Config {
+            base_url: "http://127.0.0.1:1".to_string(),
+            request_delay: Duration::ZERO,
+            cache_dir: cache_dir.clone(),
+            ..Config::default()
+        };
+        let mut heard = Vec::new();
+        let downloads = download_missing(&config, &[1, 2], |day, result| {
+            heard.push((day, result.is_ok()))
+        });
+        assert_eq!(heard, [(2, false)]);
+        assert_eq!(downloads.skipped, [1]);
+        assert_eq!(downloads.fetched, []);
+        assert_eq!(downloads.failed.keys().collect::<Vec<_>>(), [&2]);
+        fs::remove_dir_all(cache_dir).unwrap();
+    }
+
+    #[test]
+    fn test_solve_in_order() {
+        let days = [1, 2, 3, 5, 8, 13];
+        let mut finished = Vec::new();
+        solve_in_order(
+            &days,
+            3,
+            |day| {
+                thread::sleep(Duration::from_millis(20 - day as u64));
+                DayReport::failed(day, "")
+            },
+            |report| finished.push(report.day),
+        );
+        assert_eq!(finished, days);
+    }
+}
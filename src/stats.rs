@@ -0,0 +1,84 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    time::Duration,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn new(samples: &[Duration]) -> Option<Self> {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+        let min = *sorted.first()?;
+        let median = match n % 2 {
+            0 => (sorted[n / 2 - 1] + sorted[n / 2]) / 2,
+            _ => sorted[n / 2],
+        };
+        let mean = sorted.iter().sum::<Duration>() / n as u32;
+        let variance = sorted
+            .iter()
+            .map(|d| (d.as_nanos() as f64 - mean.as_nanos() as f64).powi(2))
+            .sum::<f64>()
+            / n as f64;
+        Some(Self {
+            runs: n,
+            min,
+            median,
+            mean,
+            stddev: Duration::from_nanos(variance.sqrt().round() as u64),
+        })
+    }
+
+    pub fn to_json(&self) -> String {
+        format!(
+            r#"{{"runs":{},"min_ns":{},"median_ns":{},"mean_ns":{},"stddev_ns":{}}}"#,
+            self.runs,
+            self.min.as_nanos(),
+            self.median.as_nanos(),
+            self.mean.as_nanos(),
+            self.stddev.as_nanos()
+        )
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "min {}ms, median {}ms, mean {}ms, stddev {}ms over {} runs",
+            1000.0 * self.min.as_secs_f32(),
+            1000.0 * self.median.as_secs_f32(),
+            1000.0 * self.mean.as_secs_f32(),
+            1000.0 * self.stddev.as_secs_f32(),
+            self.runs
+        )
+    }
+}
+
+#[cfg(test)]
+mod test_stats {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples = [5, 2, 4, 4, 5, 7, 9, 4].map(Duration::from_micros);
+        let stats = Stats::new(&samples).unwrap();
+        assert_eq!(stats.runs, 8);
+        assert_eq!(stats.min, Duration::from_micros(2));
+        assert_eq!(
+            stats.median,
+            Duration::from_micros(4) + Duration::from_nanos(500)
+        );
+        assert_eq!(stats.mean, Duration::from_micros(5));
+        assert_eq!(stats.stddev, Duration::from_micros(2));
+        assert_eq!(Stats::new(&[]), None);
+    }
+}
@@ -0,0 +1,394 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::{
+    fmt::{self, Display, Formatter},
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use crate::{answer::Answer, answers, config::Config, day::Part, registry::Solution, web::Fetcher};
+
+lazy_static! {
+    static ref WAIT: Regex = Regex::new(r"You have (.+?) left to wait").unwrap();
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    Wait(String),
+    AlreadySolved,
+}
+
+impl Verdict {
+    pub fn parse(response: &str) -> Option<Self> {
+        if response.contains("That's the right answer") {
+            Some(Self::Correct)
+        } else if response.contains("That's not the right answer") {
+            if response.contains("your answer is too high") {
+                Some(Self::TooHigh)
+            } else if response.contains("your answer is too low") {
+                Some(Self::TooLow)
+            } else {
+                Some(Self::Incorrect)
+            }
+        } else if response.contains("You gave an answer too recently") {
+            let wait = WAIT
+                .captures(response)
+                .map_or("a while", |c| c.get(1).unwrap().as_str());
+            Some(Self::Wait(wait.to_string()))
+        } else if response.contains("You don't seem to be solving the right level") {
+            Some(Self::AlreadySolved)
+        } else {
+            None
+        }
+    }
+
+    // Only verdicts that say something about the answer or the part are kept in the history.
+    fn name(&self) -> Option<&'static str> {
+        match self {
+            Self::Correct => Some("correct"),
+            Self::TooHigh => Some("too-high"),
+            Self::TooLow => Some("too-low"),
+            Self::Incorrect => Some("incorrect"),
+            Self::AlreadySolved => Some("already-solved"),
+            Self::Wait(_) => None,
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "correct" => Some(Self::Correct),
+            "too-high" => Some(Self::TooHigh),
+            "too-low" => Some(Self::TooLow),
+            "incorrect" => Some(Self::Incorrect),
+            "already-solved" => Some(Self::AlreadySolved),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Correct => write!(f, "That's the right answer!"),
+            Self::TooHigh => write!(f, "That's not the right answer; it's too high"),
+            Self::TooLow => write!(f, "That's not the right answer; it's too low"),
+            Self::Incorrect => write!(f, "That's not the right answer"),
+            Self::Wait(wait) => write!(f, "Answered too recently; try again in {}", wait),
+            Self::AlreadySolved => write!(f, "That part has already been solved"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Warning {
+    Rejected(Verdict),
+    NotBelow(Answer),
+    NotAbove(Answer),
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Rejected(verdict) => write!(f, "it was already rejected [{}]", verdict),
+            Self::NotBelow(too_high) => write!(f, "{} was already too high", too_high),
+            Self::NotAbove(too_low) => write!(f, "{} was already too low", too_low),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub part: Part,
+    pub answer: Answer,
+    pub verdict: Verdict,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct History {
+    pub submissions: Vec<Submission>,
+}
+
+impl History {
+    fn path(config: &Config, day: usize) -> PathBuf {
+        config.cache_file(format!("day{}.submissions", day))
+    }
+
+    pub fn parse(string: &str) -> Self {
+        let submissions = string
+            .lines()
+            .filter_map(|line| {
+                let (key, answer) = line.split_once(": ")?;
+                let (part, verdict) = key.split_once(' ')?;
+                let part = match part {
+                    "part1" => Part::One,
+                    "part2" => Part::Two,
+                    _ => return None,
+                };
+                Some(Submission {
+                    part,
+                    answer: Answer::parse(answer),
+                    verdict: Verdict::from_name(verdict)?,
+                })
+            })
+            .collect();
+        Self { submissions }
+    }
+
+    pub fn load(config: &Config, day: usize) -> Self {
+        Self::parse(&fs::read_to_string(Self::path(config, day)).unwrap_or_default())
+    }
+
+    pub fn record(
+        &mut self,
+        config: &Config,
+        day: usize,
+        submission: Submission,
+    ) -> io::Result<()> {
+        if let Some(name) = submission.verdict.name() {
+            let mut file = fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(Self::path(config, day))?;
+            writeln!(
+                file,
+                "part{} {}: {}",
+                submission.part.number(),
+                name,
+                submission.answer
+            )?;
+            self.submissions.push(submission);
+        }
+        Ok(())
+    }
+
+    // Checks an answer against the earlier verdicts for the same part, including the bounds given
+    // by numeric answers that were too high or too low.
+    pub fn check(&self, part: Part, answer: &Answer) -> Option<Warning> {
+        let guesses = self.submissions.iter().filter(|s| s.part == part);
+        if let Some(guess) = guesses.clone().find(|s| s.answer == *answer) {
+            return match guess.verdict {
+                Verdict::Correct => None,
+                _ => Some(Warning::Rejected(guess.verdict.clone())),
+            };
+        }
+        let value = answer.as_integer()?;
+        let bounds = |verdict| {
+            guesses
+                .clone()
+                .filter(move |s| s.verdict == verdict)
+                .filter_map(|s| Some((s.answer.as_integer()?, &s.answer)))
+        };
+        if let Some((bound, too_high)) = bounds(Verdict::TooHigh).min_by_key(|&(n, _)| n) {
+            if value >= bound {
+                return Some(Warning::NotBelow(too_high.clone()));
+            }
+        }
+        if let Some((bound, too_low)) = bounds(Verdict::TooLow).max_by_key(|&(n, _)| n) {
+            if value <= bound {
+                return Some(Warning::NotAbove(too_low.clone()));
+            }
+        }
+        None
+    }
+
+    // A part is done once an answer was accepted, or the site said it was already solved (by an
+    // answer submitted some other way).
+    pub fn accepted(&self, part: Part) -> Option<&Submission> {
+        self.submissions.iter().find(|s| {
+            s.part == part && matches!(s.verdict, Verdict::Correct | Verdict::AlreadySolved)
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    // the part was already done, by the submission given
+    AlreadyDone(Submission),
+    Submitted {
+        submission: Submission,
+        // what was wrong with the answer, when it was submitted anyway
+        warning: Option<Warning>,
+        // anything that couldn't be written to the answer or submission files
+        unrecorded: Vec<String>,
+    },
+}
+
+// Solves a part and submits the answer, unless the part is already done or an earlier verdict
+// rules the answer out (which `force` overrides). The verdict is added to the history, and a
+// correct answer is written to the answer file too.
+pub fn submit(
+    config: &Config,
+    solution: &dyn Solution,
+    part: Part,
+    force: bool,
+) -> Result<Outcome, String> {
+    let day = solution.day();
+    let mut history = History::load(config, day);
+    if let Some(accepted) = history.accepted(part) {
+        return Ok(Outcome::AlreadyDone(accepted.clone()));
+    }
+    let input = solution.get_input(config).map_err(|err| err.to_string())?;
+    let report = solution.solve(&input, Some(part));
+    let answer = match report.parts.first() {
+        Some(part) => part.answer.clone(),
+        None => return Err(report.error.unwrap_or_default()),
+    };
+    let warning = history.check(part, &answer);
+    if let (Some(warning), false) = (&warning, force) {
+        return Err(format!(
+            "Not submitting {} for part {} of day {}: {} (pass --force to submit anyway)",
+            answer,
+            part.number(),
+            day,
+            warning
+        ));
+    }
+    let verdict = Fetcher::new(config)
+        .submit_answer(day, part, &answer)
+        .map_err(|err| err.to_string())?;
+    let mut unrecorded = Vec::new();
+    if verdict == Verdict::Correct {
+        if let Err(err) = answers::record(config, &report) {
+            unrecorded.push(format!("Couldn't record answers for day {} [{}]", day, err));
+        }
+    }
+    let submission = Submission {
+        part,
+        answer,
+        verdict,
+    };
+    if let Err(err) = history.record(config, day, submission.clone()) {
+        unrecorded.push(format!(
+            "Couldn't record submission for day {} [{}]",
+            day, err
+        ));
+    }
+    Ok(Outcome::Submitted {
+        submission,
+        warning,
+        unrecorded,
+    })
+}
+
+#[cfg(test)]
+mod test_submissions {
+    use super::*;
+    use crate::{day02::Day02, registry::DaySolution};
+    use indoc::indoc;
+    use std::{env, process};
+
+    #[test]
+    fn test_parse_verdict() {
+        let response = |message| format!("<main>\n<article><p>{}</p></article>\n</main>", message);
+        assert_eq!(
+            Verdict::parse(&response(
+                "That's the right answer! You are one gold star closer."
+            )),
+            Some(Verdict::Correct)
+        );
+        assert_eq!(
+            Verdict::parse(&response(
+                "That's not the right answer; your answer is too high.  If you're stuck, ..."
+            )),
+            Some(Verdict::TooHigh)
+        );
+        assert_eq!(
+            Verdict::parse(&response(
+                "You gave an answer too recently; you have to wait after submitting an answer \
+                before trying again.  You have 4m 12s left to wait."
+            )),
+            Some(Verdict::Wait("4m 12s".to_string()))
+        );
+        assert_eq!(
+            Verdict::parse(&response(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )),
+            Some(Verdict::AlreadySolved)
+        );
+        assert_eq!(Verdict::parse("<html>404 Not Found</html>"), None);
+    }
+
+    #[test]
+    fn test_parse_history() {
+        let history = History::parse("part1 too-low: 1200\npart1 correct: 1351\n");
+        assert_eq!(history.submissions.len(), 2);
+        assert_eq!(history.submissions[0].verdict, Verdict::TooLow);
+        let accepted = history.accepted(Part::One).unwrap();
+        assert_eq!(accepted.answer, Answer::Int(1351));
+        assert_eq!(history.accepted(Part::Two), None);
+
+        let history = History::parse("part2 incorrect: 12\npart2 already-solved: 15\n");
+        let accepted = history.accepted(Part::Two).unwrap();
+        assert_eq!(accepted.verdict, Verdict::AlreadySolved);
+        assert_eq!(accepted.answer, Answer::Int(15));
+    }
+
+    #[test]
+    fn test_check_answer() {
+        let history = History::parse(indoc! {"
+            part1 too-high: 5000
+            part1 too-low: 1200
+            part1 too-high: 3000
+            part1 incorrect: 2000
+            part2 incorrect: ABCDEFGH
+        "});
+        let check = |part, answer: Answer| history.check(part, &answer);
+        assert_eq!(check(Part::One, Answer::Int(2500)), None);
+        assert_eq!(
+            check(Part::One, Answer::Int(2000)),
+            Some(Warning::Rejected(Verdict::Incorrect))
+        );
+        assert_eq!(
+            check(Part::One, Answer::Int(4000)),
+            Some(Warning::NotBelow(Answer::Int(3000)))
+        );
+        assert_eq!(
+            check(Part::One, Answer::Int(1200)),
+            Some(Warning::Rejected(Verdict::TooLow))
+        );
+        assert_eq!(
+            check(Part::One, Answer::Int(900)),
+            Some(Warning::NotAbove(Answer::Int(1200)))
+        );
+        assert_eq!(check(Part::Two, Answer::Int(4000)), None);
+        assert_eq!(
+            check(Part::Two, Answer::from("ABCDEFGH")),
+            Some(Warning::Rejected(Verdict::Incorrect))
+        );
+    }
+
+    #[test]
+    fn test_submit_without_sending() {
+        let cache_dir = env::temp_dir().join(format!("aoc2022-submit-{}", process::id()));
+        fs::create_dir_all(&cache_dir).unwrap();
+        fs::write(cache_dir.join("day2.in"), "A Y\nB X\nC Z\n").unwrap();
+        fs::write(
+            cache_dir.join("day2.submissions"),
+            "part1 correct: 15\npart2 too-high: 12\n",
+        )
+        .unwrap();
+        let config = Config {
+            cache_dir: cache_dir.clone(),
+            ..Config::default()
+        };
+        let solution = DaySolution::<Day02>::new();
+
+        match submit(&config, &solution, Part::One, false).unwrap() {
+            Outcome::AlreadyDone(submission) => assert_eq!(submission.answer, Answer::Int(15)),
+            outcome => panic!("expected part 1 to be done, got {:?}", outcome),
+        }
+        let err = submit(&config, &solution, Part::Two, false).unwrap_err();
+        assert_eq!(
+            err,
+            "Not submitting 12 for part 2 of day 2: \
+            it was already rejected [That's not the right answer; it's too high] \
+            (pass --force to submit anyway)"
+        );
+        fs::remove_dir_all(cache_dir).unwrap();
+    }
+}
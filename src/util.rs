@@ -48,6 +48,7 @@ pub fn decode4x6char<F: Fn(usize, usize) -> bool>(pixel: F) -> Option<char> {
     }
 }
 
+#[derive(Clone)]
 pub struct Tree<K, V> {
     pub value: V,
     pub children: HashMap<K, Tree<K, V>>,
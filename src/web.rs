@@ -0,0 +1,260 @@
+use reqwest::{
+    blocking::{Client, RequestBuilder},
+    header::{COOKIE, USER_AGENT},
+    StatusCode,
+};
+use std::{env, fs, path::PathBuf, thread, time::Instant};
+
+use crate::{
+    answer::Answer,
+    config::Config,
+    day::{Error, Part},
+    submissions::Verdict,
+};
+
+pub const AGENT: &str = concat!(
+    "github.com/Luminiscental/aoc2022 ",
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION")
+);
+
+// The cookie is taken from the first of AOC_SESSION, .session_cookie in the cache directory and
+// the config file that has one.
+pub fn session_cookie(config: &Config) -> Result<String, Error> {
+    find_session_cookie(config, Config::path(), |name| env::var(name).ok())
+}
+
+fn find_session_cookie<F: Fn(&str) -> Option<String>>(
+    config: &Config,
+    config_path: Option<PathBuf>,
+    var: F,
+) -> Result<String, Error> {
+    let mut checked = Vec::new();
+    match var("AOC_SESSION") {
+        Some(cookie) if !cookie.trim().is_empty() => return Ok(cookie.trim().to_string()),
+        Some(_) => checked.push("AOC_SESSION environment variable [empty]".to_string()),
+        None => checked.push("AOC_SESSION environment variable [not set]".to_string()),
+    }
+    let path = config.cache_file(".session_cookie".to_string());
+    match fs::read_to_string(&path) {
+        Ok(cookie) if !cookie.trim().is_empty() => return Ok(cookie.trim().to_string()),
+        Ok(_) => checked.push(format!("{} [empty]", path.display())),
+        Err(err) => checked.push(format!("{} [{}]", path.display(), err)),
+    }
+    match (&config.session, config_path) {
+        (Some(cookie), _) => return Ok(cookie.clone()),
+        (None, Some(path)) if !path.exists() => {
+            checked.push(format!("{} [not found]", path.display()))
+        }
+        (None, Some(path)) => checked.push(format!("{} [no session setting]", path.display())),
+        (None, None) => checked.push("config file [no home directory]".to_string()),
+    }
+    Err(Error::MissingCookie(checked))
+}
+
+// Only a successful response is treated as input; known error pages are turned into errors so
+// they never end up in the cache.
+pub fn check_input(status: StatusCode, body: String) -> Result<String, Error> {
+    if body.starts_with("Please don't repeatedly request this endpoint before it unlocks") {
+        Err(Error::NotUnlocked)
+    } else if body.contains("Please log in") {
+        Err(Error::SessionRejected)
+    } else if !status.is_success() {
+        Err(Error::BadStatus(status, body))
+    } else {
+        Ok(body)
+    }
+}
+
+// Keeps at least the configured delay between consecutive requests to the server.
+pub struct Fetcher<'c> {
+    config: &'c Config,
+    client: Client,
+    last_request: Option<Instant>,
+}
+
+impl<'c> Fetcher<'c> {
+    pub fn new(config: &'c Config) -> Self {
+        Self {
+            config,
+            client: Client::new(),
+            last_request: None,
+        }
+    }
+
+    fn wait(&mut self) {
+        if let Some(last_request) = self.last_request {
+            let since = last_request.elapsed();
+            if since < self.config.request_delay {
+                thread::sleep(self.config.request_delay - since);
+            }
+        }
+        self.last_request = Some(Instant::now());
+    }
+
+    fn send(
+        &mut self,
+        request: RequestBuilder,
+        cookie: Option<String>,
+    ) -> reqwest::Result<(StatusCode, String)> {
+        self.wait();
+        let mut request = request.header(USER_AGENT, AGENT);
+        if let Some(cookie) = cookie {
+            request = request.header(COOKIE, format!("session={}", cookie));
+        }
+        let response = request.send()?;
+        Ok((response.status(), response.text()?))
+    }
+
+    pub fn download_input(&mut self, day: usize) -> Result<String, Error> {
+        let cookie = session_cookie(self.config)?;
+        let request = self.client.get(self.config.input_url(day));
+        let (status, body) = self
+            .send(request, Some(cookie))
+            .map_err(Error::DownloadingInput)?;
+        check_input(status, body)
+    }
+
+    // The puzzle page is available without logging in, but only shows part 2 with a session
+    // cookie for someone who has solved part 1.
+    pub fn download_page(&mut self, day: usize) -> Result<String, Error> {
+        let cookie = session_cookie(self.config).ok();
+        let request = self.client.get(self.config.page_url(day));
+        let (status, body) = self.send(request, cookie).map_err(Error::DownloadingPage)?;
+        match status.is_success() {
+            true => Ok(body),
+            false => Err(Error::BadStatus(status, body)),
+        }
+    }
+
+    pub fn submit_answer(
+        &mut self,
+        day: usize,
+        part: Part,
+        answer: &Answer,
+    ) -> Result<Verdict, Error> {
+        let cookie = session_cookie(self.config)?;
+        let request = self.client.post(self.config.answer_url(day)).form(&[
+            ("level", part.number().to_string()),
+            ("answer", answer.to_string()),
+        ]);
+        let (status, body) = self
+            .send(request, Some(cookie))
+            .map_err(Error::SubmittingAnswer)?;
+        match Verdict::parse(&body) {
+            Some(verdict) => Ok(verdict),
+            None if body.contains("Please log in") => Err(Error::SessionRejected),
+            None if !status.is_success() => Err(Error::BadStatus(status, body)),
+            None => Err(Error::UnrecognisedResponse),
+        }
+    }
+}
+
+pub fn download_input(config: &Config, day: usize) -> Result<String, Error> {
+    Fetcher::new(config).download_input(day)
+}
+
+#[cfg(test)]
+mod test_web {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_fetcher_delay() {
+        let config = Config {
+            request_delay: Duration::from_millis(50),
+            ..Config::default()
+        };
+        let mut fetcher = Fetcher::new(&config);
+        let start = Instant::now();
+        fetcher.wait();
+        assert!(start.elapsed() < Duration::from_millis(50));
+        fetcher.wait();
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_session_cookie_precedence() {
+        let cache_dir = env::temp_dir().join(format!("aoc2022-session-{}", std::process::id()));
+        fs::create_dir_all(&cache_dir).unwrap();
+        let config = Config {
+            session: Some("from-config".to_string()),
+            cache_dir: cache_dir.clone(),
+            ..Config::default()
+        };
+        let env_cookie = |name: &str| (name == "AOC_SESSION").then(|| " from-env\n".to_string());
+        let empty_env = |name: &str| (name == "AOC_SESSION").then(String::new);
+
+        assert_eq!(
+            find_session_cookie(&config, None, |_| None).unwrap(),
+            "from-config"
+        );
+        fs::write(cache_dir.join(".session_cookie"), "from-cache\n").unwrap();
+        assert_eq!(
+            find_session_cookie(&config, None, empty_env).unwrap(),
+            "from-cache"
+        );
+        assert_eq!(
+            find_session_cookie(&config, None, env_cookie).unwrap(),
+            "from-env"
+        );
+
+        fs::write(cache_dir.join(".session_cookie"), "").unwrap();
+        let config = Config {
+            session: None,
+            ..config
+        };
+        let config_path = cache_dir.join("config");
+        match find_session_cookie(&config, Some(config_path.clone()), empty_env) {
+            Err(Error::MissingCookie(checked)) => {
+                assert_eq!(checked[0], "AOC_SESSION environment variable [empty]");
+                assert!(checked[1].ends_with(".session_cookie [empty]"));
+                assert!(checked[2].ends_with("config [not found]"));
+            }
+            other => panic!("expected a missing cookie, got {:?}", other.map(|_| ())),
+        }
+        fs::write(&config_path, "").unwrap();
+        match find_session_cookie(&config, Some(config_path), empty_env) {
+            Err(Error::MissingCookie(checked)) => {
+                assert!(checked[2].ends_with("config [no session setting]"));
+            }
+            other => panic!("expected a missing cookie, got {:?}", other.map(|_| ())),
+        }
+        fs::remove_dir_all(cache_dir).unwrap();
+    }
+
+    #[test]
+    fn test_check_input() {
+        assert_eq!(
+            check_input(StatusCode::OK, "1000\n2000\n".to_string()).unwrap(),
+            "1000\n2000\n"
+        );
+        assert!(matches!(
+            check_input(
+                StatusCode::NOT_FOUND,
+                "Please don't repeatedly request this endpoint before it unlocks! \
+                The calendar countdown is synchronized with the server time; \
+                the link will be enabled on the calendar the instant this puzzle \
+                becomes available.\n"
+                    .to_string()
+            ),
+            Err(Error::NotUnlocked)
+        ));
+        assert!(matches!(
+            check_input(
+                StatusCode::BAD_REQUEST,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+                    .to_string()
+            ),
+            Err(Error::SessionRejected)
+        ));
+        assert!(matches!(
+            check_input(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Internal Server Error\n".to_string()
+            ),
+            Err(Error::BadStatus(StatusCode::INTERNAL_SERVER_ERROR, _))
+        ));
+    }
+}